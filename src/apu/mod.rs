@@ -26,6 +26,7 @@ pub mod pulse;
 pub mod wave;
 pub mod noise;
 
+use alloc::vec::Vec;
 use pulse::PulseChannel;
 use wave::WaveChannel;
 use noise::NoiseChannel;
@@ -34,7 +35,14 @@ use crate::memory_map::io_registers::*;
 /// フレームシーケンサの周期 (CPUサイクル: 4,194,304 / 512 = 8192)
 const FRAME_SEQUENCER_PERIOD: u16 = 8192;
 
+/// CPUクロック周波数 (Hz)
+pub const CPU_CLOCK_HZ: u32 = 4_194_304;
+
+/// 1フレームのCPUサイクル数 (154ライン × 456ドット、約59.7Hz)
+pub const CYCLES_PER_FRAME: u32 = 70224;
+
 /// APU (Audio Processing Unit)
+#[derive(Clone)]
 pub struct Apu {
     /// Channel 1: パルス + スイープ
     pub channel1: PulseChannel,
@@ -72,8 +80,23 @@ pub struct Apu {
     pub sample_buffer: Vec<f32>,
     /// サンプル生成用ダウンサンプルカウンタ
     downsample_counter: u32,
-    /// サンプリングレート (デフォルト: 44100Hz)
+    /// サンプリングレート (デフォルト: 44100Hz、0でサンプル生成なし)
     pub sample_rate: u32,
+    /// サンプル生成フラグ (falseならチャンネル状態のみ進める)
+    pub generate_audio: bool,
+    /// 未トリガーのチャンネルを無音(0.0)として扱う
+    /// (falseならDAC有効・出力0のチャンネルが-1.0の直流成分としてミックスされる)
+    pub silence_idle_channels: bool,
+    /// NR52で電源オフした際に長さカウンタもクリアする (CGB挙動、falseならDMG同様に保持)
+    pub clear_length_on_power_off: bool,
+    /// フレーム単位の取り出し数の端数カウンタ (ダウンサンプルと同じ方式で誤差を繰り越す)
+    frame_drain_counter: u64,
+    /// tickで進む累計CPUサイクル数 (電源オフ中も進む、レジスタ書き込みログのタイムスタンプ)
+    cycle_count: u64,
+    /// レジスタ書き込みを記録するか
+    record_registers: bool,
+    /// レジスタ書き込みログ (サイクル, アドレス, 値)
+    register_log: Vec<(u64, u16, u8)>,
 }
 
 impl Apu {
@@ -94,15 +117,37 @@ impl Apu {
             sample_buffer: Vec::new(),
             downsample_counter: 0,
             sample_rate: 44100,
+            generate_audio: true,
+            silence_idle_channels: true,
+            clear_length_on_power_off: false,
+            frame_drain_counter: 0,
+            cycle_count: 0,
+            record_registers: false,
+            register_log: Vec::new(),
         }
     }
 
-    /// APUを1 CPUサイクル進める
-    pub fn tick(&mut self) {
+    /// サンプル生成を行うかどうか（ヘッドレス実行ではfalse）
+    pub fn is_audio_enabled(&self) -> bool {
+        self.generate_audio && self.sample_rate > 0
+    }
+
+    /// APUを指定CPUサイクル分進める
+    ///
+    /// チャンネル・フレームシーケンサ・ダウンサンプリングは1サイクルずつ進めるため、
+    /// まとめて呼んでも1サイクルずつ呼んだ場合と同じ状態・サンプルになる
+    pub fn tick(&mut self, cycles: u32) {
+        self.cycle_count += cycles as u64;
         if !self.power {
             return;
         }
+        for _ in 0..cycles {
+            self.tick_cycle();
+        }
+    }
 
+    // 電源オン中のAPUを1 CPUサイクル進める
+    fn tick_cycle(&mut self) {
         // 各チャンネルの周波数タイマーを進める
         self.channel1.tick();
         self.channel2.tick();
@@ -116,10 +161,15 @@ impl Apu {
             self.clock_frame_sequencer();
         }
 
+        // サンプル生成無効時はチャンネル/フレームシーケンサのみ進める
+        if !self.is_audio_enabled() {
+            return;
+        }
+
         // ダウンサンプリング (CPUクロック→サンプリングレート)
         self.downsample_counter += self.sample_rate;
-        if self.downsample_counter >= 4_194_304 {
-            self.downsample_counter -= 4_194_304;
+        if self.downsample_counter >= CPU_CLOCK_HZ {
+            self.downsample_counter -= CPU_CLOCK_HZ;
             self.generate_sample();
         }
     }
@@ -174,10 +224,10 @@ impl Apu {
 
     /// オーディオサンプルを生成してバッファに追加
     fn generate_sample(&mut self) {
-        let ch1 = self.channel1.dac_output();
-        let ch2 = self.channel2.dac_output();
-        let ch3 = self.channel3.dac_output();
-        let ch4 = self.channel4.dac_output();
+        let ch1 = self.channel_output(self.channel1.enabled, self.channel1.dac_output());
+        let ch2 = self.channel_output(self.channel2.enabled, self.channel2.dac_output());
+        let ch3 = self.channel_output(self.channel3.enabled, self.channel3.dac_output());
+        let ch4 = self.channel_output(self.channel4.enabled, self.channel4.dac_output());
 
         // ミキシング（パニング適用）
         let mut left: f32 = 0.0;
@@ -205,9 +255,122 @@ impl Apu {
         self.sample_buffer.push(right);
     }
 
+    /// ミックス対象のチャンネル出力（未トリガーのチャンネルは設定に応じて無音）
+    fn channel_output(&self, enabled: bool, dac: f32) -> f32 {
+        if self.silence_idle_channels && !enabled {
+            0.0
+        } else {
+            dac
+        }
+    }
+
     /// サンプルバッファを取り出す（取り出し後はクリア）
     pub fn drain_samples(&mut self) -> Vec<f32> {
-        std::mem::take(&mut self.sample_buffer)
+        core::mem::take(&mut self.sample_buffer)
+    }
+
+    /// レジスタ書き込みの記録の有効/無効を切り替え (音楽の抽出ツール用)
+    ///
+    /// 有効中はwriteのたびに (サイクル, アドレス, 値) を記録する。
+    /// 電源オフで無視される書き込みやWave RAMへの書き込みも含む
+    pub fn set_register_recording(&mut self, enabled: bool) {
+        self.record_registers = enabled;
+    }
+
+    /// 記録したレジスタ書き込みを取り出す (ログは空になる)
+    pub fn take_register_log(&mut self) -> Vec<(u64, u16, u8)> {
+        core::mem::take(&mut self.register_log)
+    }
+
+    /// サンプルバッファとレジスタ書き込みログを捨てる (ステートの復元用)
+    pub(crate) fn clear_output_buffers(&mut self) {
+        self.sample_buffer.clear();
+        self.register_log.clear();
+    }
+
+    /// tickで進んだ累計CPUサイクル数
+    pub fn cycle_count(&self) -> u64 {
+        self.cycle_count
+    }
+
+    /// 1フレーム (70224サイクル) あたりに生成されるサンプル数（左右ペア単位、四捨五入）
+    ///
+    /// ダウンサンプリングはサイクル数で誤差を繰り越すため、各フレームの実際の生成数は
+    /// この値から±1以内に収まる
+    pub fn samples_per_frame(&self) -> u32 {
+        ((self.sample_rate as u64 * CYCLES_PER_FRAME as u64 + CPU_CLOCK_HZ as u64 / 2) / CPU_CLOCK_HZ as u64) as u32
+    }
+
+    /// 約1フレーム分のサンプルを先頭から取り出す（左右インターリーブ、残りはバッファに保持）
+    ///
+    /// 取り出し数の端数を繰り越すため、毎フレーム呼び出せば生成数と一致し続ける
+    pub fn drain_frame_samples(&mut self) -> Vec<f32> {
+        self.frame_drain_counter += self.sample_rate as u64 * CYCLES_PER_FRAME as u64;
+        let pairs = self.frame_drain_counter / CPU_CLOCK_HZ as u64;
+        self.frame_drain_counter %= CPU_CLOCK_HZ as u64;
+
+        let count = (pairs as usize * 2).min(self.sample_buffer.len());
+        self.sample_buffer.drain(..count).collect()
+    }
+
+    /// サウンドテスト: APUの電源を入れ、指定チャンネルだけを左右に出力して鳴らす
+    ///
+    /// - channel: 1-4（範囲外は無視）
+    /// - frequency_reg: 11bitの周波数レジスタ値（ch1-3、音程は 131072/(2048-x) Hz、ch3はその半分）。
+    ///   ch4は下位8bitをNR43（ノイズの分周・シフト幅）として使う
+    /// - duty: 0-3（ch1/ch2のデューティ比、ch3は波形の1の区間の長さ）
+    /// - volume: 0-15（ch1/ch2/ch4は初期音量、ch3は出力レベルに丸める）。減衰・長さカウンタは無効
+    pub fn play_tone(&mut self, channel: u8, frequency_reg: u16, duty: u8, volume: u8) {
+        if !(1..=4).contains(&channel) {
+            return;
+        }
+        let duty = duty & 0x03;
+        let volume = volume & 0x0F;
+        let low = frequency_reg as u8;
+        let high = 0x80 | ((frequency_reg >> 8) as u8 & 0x07); // トリガー、長さ無効
+
+        self.write(NR52, 0x80);
+        self.write(NR50, 0x77);
+        self.write(NR51, 0x11 << (channel - 1));
+
+        match channel {
+            1 => {
+                self.write(NR10, 0x00); // スイープなし
+                self.write(NR11, duty << 6);
+                self.write(NR12, volume << 4);
+                self.write(NR13, low);
+                self.write(NR14, high);
+            }
+            2 => {
+                self.write(NR21, duty << 6);
+                self.write(NR22, volume << 4);
+                self.write(NR23, low);
+                self.write(NR24, high);
+            }
+            3 => {
+                // デューティ 12.5%/25%/50%/75% に対応する矩形波を波形メモリに書く
+                let high_samples = [4, 8, 16, 24][duty as usize];
+                let samples: [u8; 32] = core::array::from_fn(|i| if i < high_samples { 0x0F } else { 0x00 });
+                self.channel3.write_dac(0x00);
+                self.channel3.set_samples(&samples);
+                // 出力レベル: 0=ミュート, 1=100%, 2=50%, 3=25%
+                let level = match volume {
+                    0 => 0,
+                    12..=15 => 1,
+                    6..=11 => 2,
+                    _ => 3,
+                };
+                self.write(NR30, 0x80);
+                self.write(NR32, level << 5);
+                self.write(NR33, low);
+                self.write(NR34, high);
+            }
+            _ => {
+                self.write(NR42, volume << 4);
+                self.write(NR43, low);
+                self.write(NR44, 0x80);
+            }
+        }
     }
 
     /// I/Oレジスタの読み取り
@@ -261,6 +424,10 @@ impl Apu {
 
     /// I/Oレジスタへの書き込み
     pub fn write(&mut self, addr: u16, value: u8) {
+        if self.record_registers {
+            self.register_log.push((self.cycle_count, addr, value));
+        }
+
         // Wave RAMはAPU電源に関係なく書き込み可能
         if (WAVE_RAM_START..=WAVE_RAM_END).contains(&addr) {
             self.channel3.write_wave_ram(addr, value);
@@ -362,7 +529,16 @@ impl Apu {
     }
 
     /// APU電源オフ時の全レジスタクリア
+    ///
+    /// DMGでは長さカウンタは電源オフの影響を受けず保持される（CGBではクリアされる）
     fn power_off(&mut self) {
+        let lengths = [
+            self.channel1.length_counter,
+            self.channel2.length_counter,
+            self.channel3.length_counter,
+            self.channel4.length_counter,
+        ];
+
         self.channel1 = PulseChannel::new(true);
         self.channel2 = PulseChannel::new(false);
         // Wave RAMは保持
@@ -371,6 +547,13 @@ impl Apu {
         self.channel3.wave_ram = wave_ram_backup;
         self.channel4 = NoiseChannel::new();
 
+        if !self.clear_length_on_power_off {
+            self.channel1.length_counter = lengths[0];
+            self.channel2.length_counter = lengths[1];
+            self.channel3.length_counter = lengths[2];
+            self.channel4.length_counter = lengths[3];
+        }
+
         self.vin_left = false;
         self.left_volume = 0;
         self.vin_right = false;
@@ -379,10 +562,29 @@ impl Apu {
     }
 }
 
+impl Default for Apu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// NR10-NR52 (0xFF10-0xFF26) と未使用領域 (0xFF27-0xFF2F) の読み取りマスク
+    ///
+    /// 読み取り値は「書き込んだ値 | マスク」になる（読めないビットは常に1）。
+    /// 実装側は各チャンネルのread_*が個別にビットを立てるため、この表で突き合わせる
+    const REGISTER_READ_MASKS: [u8; 0x20] = [
+        0x80, 0x3F, 0x00, 0xFF, 0xBF, // NR10-NR14
+        0xFF, 0x3F, 0x00, 0xFF, 0xBF, // (0xFF15), NR21-NR24
+        0x7F, 0xFF, 0x9F, 0xFF, 0xBF, // NR30-NR34
+        0xFF, 0xFF, 0x00, 0x00, 0xBF, // (0xFF1F), NR41-NR44
+        0x00, 0x00, 0x70,             // NR50-NR52
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // 0xFF27-0xFF2F
+    ];
+
     #[test]
     fn test_apu_creation() {
         let apu = Apu::new();
@@ -479,6 +681,101 @@ mod tests {
         assert_eq!(apu.read(WAVE_RAM_START + 1), 0x34);
     }
 
+    #[test]
+    fn test_apu_power_off_preserves_length_counters() {
+        let mut apu = Apu::new();
+        apu.write(NR52, 0x80);
+        apu.write(NR12, 0xF0);
+        apu.write(NR11, 0x3A); // counter = 64 - 58 = 6
+        apu.write(NR31, 0xF0); // counter = 256 - 240 = 16
+        apu.write(NR41, 0x30); // counter = 64 - 48 = 16
+        apu.write(NR14, 0xC0); // トリガー + 長さ有効
+        assert!(apu.channel1.enabled);
+
+        // DMG: 長さカウンタ以外はクリアされる
+        apu.write(NR52, 0x00);
+        assert!(!apu.channel1.enabled);
+        assert!(!apu.channel1.length_enabled);
+        assert_eq!(apu.channel1.length_counter, 6);
+        assert_eq!(apu.channel3.length_counter, 16);
+        assert_eq!(apu.channel4.length_counter, 16);
+
+        // CGB: 長さカウンタもクリアされる
+        let mut apu = Apu::new();
+        apu.clear_length_on_power_off = true;
+        apu.write(NR52, 0x80);
+        apu.write(NR11, 0x3A);
+        apu.write(NR52, 0x00);
+        assert_eq!(apu.channel1.length_counter, 0);
+    }
+
+    #[test]
+    fn test_apu_register_log() {
+        let mut apu = Apu::new();
+        // 記録無効中の書き込みは残らない
+        apu.write(NR52, 0x80);
+        assert!(apu.take_register_log().is_empty());
+
+        apu.set_register_recording(true);
+        apu.write(NR50, 0x77);
+        for _ in 0..100 {
+            apu.tick(1);
+        }
+        apu.write(NR12, 0xF0);
+        apu.write(NR14, 0x80);
+        for _ in 0..50 {
+            apu.tick(1);
+        }
+        apu.write(WAVE_RAM_START, 0x12);
+
+        let log = apu.take_register_log();
+        assert_eq!(
+            log,
+            vec![
+                (0, NR50, 0x77),
+                (100, NR12, 0xF0),
+                (100, NR14, 0x80),
+                (150, WAVE_RAM_START, 0x12),
+            ]
+        );
+        assert!(log.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert!(apu.take_register_log().is_empty());
+
+        apu.set_register_recording(false);
+        apu.write(NR51, 0xFF);
+        assert!(apu.take_register_log().is_empty());
+        assert_eq!(apu.cycle_count(), 150);
+    }
+
+    #[test]
+    fn test_apu_register_read_masks() {
+        for value in [0x00u8, 0xFF, 0x5A, 0xA5, 0x3C] {
+            for addr in NR10..=0xFF2F {
+                if addr == NR52 {
+                    continue;
+                }
+                let mut apu = Apu::new();
+                apu.write(NR52, 0x80);
+                apu.write(addr, value);
+                let expected = value | REGISTER_READ_MASKS[(addr - NR10) as usize];
+                assert_eq!(
+                    apu.read(addr),
+                    expected,
+                    "addr={:04X} value={:02X}",
+                    addr,
+                    value
+                );
+            }
+        }
+
+        // NR52は電源ビットのみ書き込み可能、bit4-6は1、下位4bitはチャンネル状態
+        let mut apu = Apu::new();
+        apu.write(NR52, 0xFF);
+        assert_eq!(apu.read(NR52), 0xF0);
+        apu.write(NR52, 0x00);
+        assert_eq!(apu.read(NR52), 0x70);
+    }
+
     #[test]
     fn test_apu_wave_ram_accessible_when_off() {
         let mut apu = Apu::new();
@@ -508,7 +805,7 @@ mod tests {
 
         // しばらくtick
         for _ in 0..44100 {
-            apu.tick();
+            apu.tick(1);
         }
 
         // サンプルが生成されているはず
@@ -523,7 +820,7 @@ mod tests {
         let mut apu = Apu::new();
         // 電源オフではtickしてもサンプルが生成されない
         for _ in 0..1000 {
-            apu.tick();
+            apu.tick(1);
         }
         let samples = apu.drain_samples();
         assert!(samples.is_empty());
@@ -556,10 +853,193 @@ mod tests {
 
         // フレームシーケンサのstep 0まで進める (8192サイクル)
         for _ in 0..8192 {
-            apu.tick();
+            apu.tick(1);
         }
 
         // 長さカウンタが消費されてチャンネル無効化
         assert!(!apu.channel1.enabled);
     }
+
+    #[test]
+    fn test_apu_bulk_tick_matches_single_ticks() {
+        let setup = |apu: &mut Apu| {
+            apu.write(NR52, 0x80);
+            apu.write(NR50, 0x77);
+            apu.write(NR51, 0xFF);
+            apu.write(NR10, 0x15); // スイープ
+            apu.write(NR11, 0x80 | 0x30);
+            apu.write(NR12, 0xF3); // エンベロープ減少
+            apu.write(NR13, 0x00);
+            apu.write(NR14, 0xC6); // トリガー + 長さ有効
+            apu.write(NR42, 0xA1);
+            apu.write(NR43, 0x24);
+            apu.write(NR44, 0x80);
+        };
+        let mut bulk = Apu::new();
+        let mut single = Apu::new();
+        setup(&mut bulk);
+        setup(&mut single);
+
+        // 命令単位の半端なサイクル数で、フレームシーケンサとダウンサンプリングの境界をまたぐ
+        for cycles in [4u32, 8, 12, 20, 24, 7, 8191, 70224].iter().cycle().take(200) {
+            bulk.tick(*cycles);
+            for _ in 0..*cycles {
+                single.tick(1);
+            }
+        }
+
+        assert_eq!(bulk.cycle_count(), single.cycle_count());
+        assert_eq!(bulk.frame_sequencer_step, single.frame_sequencer_step);
+        assert_eq!(bulk.frame_sequencer_timer, single.frame_sequencer_timer);
+        for addr in 0xFF10..=0xFF3F {
+            assert_eq!(bulk.read(addr), single.read(addr), "addr={:04X}", addr);
+        }
+        assert!(!bulk.sample_buffer.is_empty());
+        assert_eq!(bulk.sample_buffer, single.sample_buffer);
+    }
+
+    #[test]
+    fn test_apu_audio_disabled_keeps_length_timing() {
+        let mut apu = Apu::new();
+        apu.generate_audio = false;
+        apu.write(NR52, 0x80);
+
+        apu.write(NR22, 0xF0); // DAC有効
+        apu.write(NR21, 0x3E); // 長さカウンタ = 64 - 62 = 2
+        apu.write(NR24, 0xC0); // トリガー + 長さ有効
+        assert_eq!(apu.read(NR52) & 0x02, 0x02);
+
+        // ステップ0とステップ2で長さカウンタがクロックされ、3ステップ目で失効
+        for _ in 0..(FRAME_SEQUENCER_PERIOD as u32 * 3 - 1) {
+            apu.tick(1);
+        }
+        assert_eq!(apu.read(NR52) & 0x02, 0x02);
+        apu.tick(1);
+        assert_eq!(apu.read(NR52) & 0x02, 0x00);
+
+        // サンプルは一切生成されない
+        assert!(apu.sample_buffer.is_empty());
+    }
+
+    #[test]
+    fn test_apu_sample_rate_zero_generates_no_samples() {
+        let mut apu = Apu::new();
+        apu.sample_rate = 0;
+        apu.write(NR52, 0x80);
+
+        for _ in 0..44100 {
+            apu.tick(1);
+        }
+
+        assert!(!apu.is_audio_enabled());
+        assert!(apu.drain_samples().is_empty());
+    }
+
+    #[test]
+    fn test_apu_samples_per_frame() {
+        let mut apu = Apu::new();
+        // 44100 * 70224 / 4194304 ≒ 738.3
+        assert_eq!(apu.samples_per_frame(), 738);
+
+        apu.sample_rate = 48000;
+        // 48000 * 70224 / 4194304 ≒ 803.6
+        assert_eq!(apu.samples_per_frame(), 804);
+    }
+
+    #[test]
+    fn test_apu_one_frame_sample_count() {
+        let mut apu = Apu::new();
+        apu.write(NR52, 0x80);
+        let expected = apu.samples_per_frame() as i64;
+
+        // 複数フレームにわたり、各フレームの生成数が±1以内に収まる
+        for _ in 0..10 {
+            for _ in 0..CYCLES_PER_FRAME {
+                apu.tick(1);
+            }
+            let samples = apu.drain_frame_samples();
+            assert_eq!(samples.len() % 2, 0);
+            let pairs = (samples.len() / 2) as i64;
+            assert!((pairs - expected).abs() <= 1, "pairs={} expected={}", pairs, expected);
+            // 取り出し数は生成数に追従し、バッファに溜まり続けない
+            assert!(apu.sample_buffer.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_apu_drain_frame_samples_keeps_remainder() {
+        let mut apu = Apu::new();
+        // 初回は端数切り捨てで738ペア
+        apu.sample_buffer = vec![0.0; 738 * 2 + 10];
+
+        assert_eq!(apu.drain_frame_samples().len(), 738 * 2);
+        assert_eq!(apu.sample_buffer.len(), 10);
+        assert_eq!(apu.drain_frame_samples().len(), 10);
+        assert!(apu.sample_buffer.is_empty());
+    }
+
+    #[test]
+    fn test_apu_play_tone_channel2() {
+        let mut apu = Apu::new();
+        // 周波数レジスタ1750: 131072 / (2048 - 1750) ≒ 440Hz
+        apu.play_tone(2, 1750, 2, 15);
+        assert!(apu.power);
+        assert!(apu.channel2.enabled);
+        assert!(!apu.channel1.enabled);
+
+        // 0.1秒分
+        for _ in 0..CPU_CLOCK_HZ / 10 {
+            apu.tick(1);
+        }
+        let samples = apu.drain_samples();
+        let left: Vec<f32> = samples.iter().step_by(2).copied().collect();
+
+        // 無音ではない
+        let max = left.iter().cloned().fold(f32::MIN, f32::max);
+        let min = left.iter().cloned().fold(f32::MAX, f32::min);
+        assert!(max > min);
+
+        // 低→高の立ち上がり回数から周期を求める（0.1秒で約44回）
+        let mid = (max + min) / 2.0;
+        let rises = left.windows(2).filter(|w| w[0] < mid && w[1] >= mid).count();
+        assert!((42..=46).contains(&rises), "rises={}", rises);
+
+        // 無効なチャンネル番号は何もしない
+        let mut apu = Apu::new();
+        apu.play_tone(5, 1750, 2, 15);
+        assert!(!apu.power);
+    }
+
+    #[test]
+    fn test_apu_idle_channels_are_silent() {
+        let mut apu = Apu::new();
+        apu.write(NR52, 0x80);
+        apu.write(NR50, 0x77);
+        apu.write(NR51, 0xFF);
+
+        // 全DACオフ: 完全な無音
+        for _ in 0..CYCLES_PER_FRAME {
+            apu.tick(1);
+        }
+        let samples = apu.drain_samples();
+        assert!(!samples.is_empty());
+        assert!(samples.iter().all(|&s| s == 0.0));
+
+        // DAC有効だが未トリガー: 直流成分なしの無音
+        apu.write(NR12, 0xF0);
+        apu.write(NR22, 0xF0);
+        apu.write(NR30, 0x80);
+        apu.write(NR42, 0xF0);
+        for _ in 0..CYCLES_PER_FRAME {
+            apu.tick(1);
+        }
+        assert!(apu.drain_samples().iter().all(|&s| s == 0.0));
+
+        // 無効化すると従来どおりDACの直流成分がミックスされる
+        apu.silence_idle_channels = false;
+        for _ in 0..CYCLES_PER_FRAME {
+            apu.tick(1);
+        }
+        assert!(apu.drain_samples().iter().all(|&s| s < 0.0));
+    }
 }
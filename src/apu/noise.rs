@@ -17,6 +17,7 @@
 //   Bit 6:   長さ有効
 
 /// ノイズチャンネル
+#[derive(Clone)]
 pub struct NoiseChannel {
     /// チャンネル有効フラグ
     pub enabled: bool,
@@ -148,6 +149,14 @@ impl NoiseChannel {
         self.envelope_timer = if self.envelope_period == 0 { 8 } else { self.envelope_period };
     }
 
+    /// LFSRのクロック周波数 (Hz、デバッグ表示用)
+    ///
+    /// 4194304 / (除数 << シフト)。除数コード0は除数8として扱う (= 262144 / 0.5 / 2^s)
+    pub fn lfsr_rate_hz(&self) -> f32 {
+        let period = (DIVISOR_TABLE[self.divisor_code as usize] as u32) << self.clock_shift;
+        super::CPU_CLOCK_HZ as f32 / period as f32
+    }
+
     /// 周波数タイマー周期を計算
     fn get_period(&self) -> u16 {
         DIVISOR_TABLE[self.divisor_code as usize] << self.clock_shift
@@ -201,6 +210,17 @@ impl NoiseChannel {
         }
     }
 
+    /// 現在のLFSR値 (15ビット)
+    pub fn lfsr_value(&self) -> u16 {
+        self.lfsr
+    }
+
+    /// LFSRを任意の値に設定（テスト用。実機ではトリガー時に常に0x7FFFから開始）
+    #[cfg(test)]
+    pub fn set_lfsr(&mut self, value: u16) {
+        self.lfsr = value & 0x7FFF;
+    }
+
     /// 現在の出力サンプル (0-15)
     pub fn output(&self) -> u8 {
         if !self.enabled || !self.dac_enabled {
@@ -224,10 +244,29 @@ impl NoiseChannel {
     }
 }
 
+impl Default for NoiseChannel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_noise_lfsr_rate_hz() {
+        let mut ch = NoiseChannel::new();
+        ch.write_polynomial(0x00); // 除数コード0, シフト0
+        assert_eq!(ch.lfsr_rate_hz(), 524288.0);
+        ch.write_polynomial(0x01); // 除数コード1 (16)
+        assert_eq!(ch.lfsr_rate_hz(), 262144.0);
+        ch.write_polynomial(0x53); // シフト5, 除数コード3 (48)
+        assert!((ch.lfsr_rate_hz() - 262144.0 / 3.0 / 32.0).abs() < 0.01);
+        ch.write_polynomial(0xF7); // シフト15, 除数コード7 (112)
+        assert!((ch.lfsr_rate_hz() - 4194304.0 / (112.0 * 32768.0)).abs() < 0.001);
+    }
+
     #[test]
     fn test_noise_channel_creation() {
         let ch = NoiseChannel::new();
@@ -320,4 +359,43 @@ mod tests {
         assert_eq!(ch.output(), 0);
         assert_eq!(ch.dac_output(), 0.0);
     }
+
+    #[test]
+    fn test_noise_lfsr_sequence_15bit() {
+        let mut ch = NoiseChannel::new();
+        ch.write_envelope(0xF0);
+        ch.write_polynomial(0x00); // shift=0, 15bit, divisor=0 (周期8サイクル)
+        ch.write_control(0x80); // トリガー
+        assert_eq!(ch.lfsr_value(), 0x7FFF);
+
+        let expected = [
+            0x3FFF, 0x1FFF, 0x0FFF, 0x07FF, 0x03FF, 0x01FF, 0x00FF, 0x007F,
+            0x003F, 0x001F, 0x000F, 0x0007, 0x0003, 0x0001, 0x4000, 0x2000,
+        ];
+        for &value in &expected {
+            for _ in 0..8 {
+                ch.tick();
+            }
+            assert_eq!(ch.lfsr_value(), value);
+        }
+    }
+
+    #[test]
+    fn test_noise_lfsr_seed_7bit() {
+        let mut ch = NoiseChannel::new();
+        ch.write_polynomial(0x08); // 7bitモード, divisor=0
+        ch.write_control(0x80);
+        ch.set_lfsr(0x0001);
+
+        // XOR結果はbit14とbit6の両方に入る
+        for _ in 0..8 {
+            ch.tick();
+        }
+        assert_eq!(ch.lfsr_value(), 0x4040);
+
+        for _ in 0..8 {
+            ch.tick();
+        }
+        assert_eq!(ch.lfsr_value(), 0x2020);
+    }
 }
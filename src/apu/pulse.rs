@@ -23,6 +23,7 @@ const DUTY_TABLE: [[u8; 8]; 4] = [
 ];
 
 /// パルスチャンネル
+#[derive(Clone)]
 pub struct PulseChannel {
     /// チャンネル有効フラグ
     pub enabled: bool,
@@ -155,6 +156,13 @@ impl PulseChannel {
         }
     }
 
+    /// 現在の周波数レジスタ値に対応する音の高さ (Hz、デバッグ表示用)
+    ///
+    /// 131072 / (2048 - frequency)。デューティ8ステップで1周期
+    pub fn frequency_hz(&self) -> f32 {
+        131072.0 / (2048 - self.frequency as u32) as f32
+    }
+
     /// NRx3 周波数下位レジスタへの書き込み (書き込みのみ)
     pub fn write_frequency_low(&mut self, value: u8) {
         self.frequency = (self.frequency & 0x700) | value as u16;
@@ -288,6 +296,11 @@ impl PulseChannel {
         }
     }
 
+    /// 現在のデューティステップ位置 (0-7)
+    pub fn duty_position(&self) -> u8 {
+        self.duty_position
+    }
+
     /// 現在の出力サンプル (0-15)
     pub fn output(&self) -> u8 {
         if !self.enabled || !self.dac_enabled {
@@ -311,6 +324,17 @@ impl PulseChannel {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pulse_frequency_hz() {
+        let mut ch = PulseChannel::new(false);
+        ch.frequency = 1750; // A4付近
+        assert!((ch.frequency_hz() - 439.8).abs() < 0.1);
+        ch.frequency = 0;
+        assert_eq!(ch.frequency_hz(), 64.0);
+        ch.frequency = 2047;
+        assert_eq!(ch.frequency_hz(), 131072.0);
+    }
+
     #[test]
     fn test_pulse_channel_creation() {
         let ch = PulseChannel::new(false);
@@ -418,4 +442,22 @@ mod tests {
         assert_eq!(ch.output(), 0);
         assert_eq!(ch.dac_output(), 0.0);
     }
+
+    #[test]
+    fn test_trigger_preserves_duty_position() {
+        let mut ch = PulseChannel::new(false);
+        ch.write_envelope(0xF0);
+        ch.write_frequency_low(0xFF);
+        ch.write_frequency_high(0x87); // 周波数0x7FF (4サイクル/ステップ) + トリガー
+        assert_eq!(ch.duty_position(), 0);
+
+        for _ in 0..12 {
+            ch.tick();
+        }
+        assert_eq!(ch.duty_position(), 3);
+
+        // 実機では再トリガーしてもデューティ位置はリセットされない
+        ch.write_frequency_high(0x87);
+        assert_eq!(ch.duty_position(), 3);
+    }
 }
@@ -13,6 +13,7 @@
 // Wave RAM (0xFF30-0xFF3F): 16バイト = 32サンプル (各4ビット)
 
 /// ウェーブチャンネル
+#[derive(Clone)]
 pub struct WaveChannel {
     /// チャンネル有効フラグ
     pub enabled: bool,
@@ -84,6 +85,13 @@ impl WaveChannel {
         self.output_level = (value >> 5) & 0x03;
     }
 
+    /// 現在の周波数レジスタ値に対応する音の高さ (Hz、デバッグ表示用)
+    ///
+    /// 65536 / (2048 - frequency)。Wave RAMの32サンプルで1周期
+    pub fn frequency_hz(&self) -> f32 {
+        65536.0 / (2048 - self.frequency as u32) as f32
+    }
+
     /// NR33 周波数下位レジスタへの書き込み (書き込みのみ)
     pub fn write_frequency_low(&mut self, value: u8) {
         self.frequency = (self.frequency & 0x700) | value as u16;
@@ -122,6 +130,23 @@ impl WaveChannel {
         }
     }
 
+    /// Wave RAMを32サンプル (各0-15) として設定（上位ニブルが先のサンプル）
+    pub fn set_samples(&mut self, samples: &[u8; 32]) {
+        for (byte, pair) in self.wave_ram.iter_mut().zip(samples.chunks_exact(2)) {
+            *byte = ((pair[0] & 0x0F) << 4) | (pair[1] & 0x0F);
+        }
+    }
+
+    /// Wave RAMを32サンプル (各0-15) として取得
+    pub fn get_samples(&self) -> [u8; 32] {
+        let mut samples = [0u8; 32];
+        for (i, &byte) in self.wave_ram.iter().enumerate() {
+            samples[i * 2] = byte >> 4;
+            samples[i * 2 + 1] = byte & 0x0F;
+        }
+        samples
+    }
+
     /// チャンネルトリガー
     fn trigger(&mut self) {
         self.enabled = self.dac_enabled;
@@ -193,10 +218,26 @@ impl WaveChannel {
     }
 }
 
+impl Default for WaveChannel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_wave_frequency_hz() {
+        let mut ch = WaveChannel::new();
+        // パルスと同じレジスタ値なら1オクターブ低い
+        ch.frequency = 1750;
+        assert!((ch.frequency_hz() - 219.9).abs() < 0.1);
+        ch.frequency = 1024;
+        assert_eq!(ch.frequency_hz(), 64.0);
+    }
+
     #[test]
     fn test_wave_channel_creation() {
         let ch = WaveChannel::new();
@@ -289,4 +330,24 @@ mod tests {
         assert_eq!(ch.output(), 0);
         assert_eq!(ch.dac_output(), 0.0);
     }
+
+    #[test]
+    fn test_wave_set_get_samples() {
+        let mut ch = WaveChannel::new();
+
+        // 0,1,2,...,15,15,14,...,0 の三角波
+        let mut samples = [0u8; 32];
+        for i in 0..16 {
+            samples[i] = i as u8;
+            samples[31 - i] = i as u8;
+        }
+        ch.set_samples(&samples);
+
+        assert_eq!(ch.read_wave_ram(0xFF30), 0x01);
+        assert_eq!(ch.read_wave_ram(0xFF31), 0x23);
+        assert_eq!(ch.read_wave_ram(0xFF37), 0xEF);
+        assert_eq!(ch.read_wave_ram(0xFF38), 0xFE);
+        assert_eq!(ch.read_wave_ram(0xFF3F), 0x10);
+        assert_eq!(ch.get_samples(), samples);
+    }
 }
@@ -0,0 +1,178 @@
+// src/boot_animation.rs
+// 起動ロゴのスクロールアニメーション（BootROMなしで再現）
+//
+// カートリッジヘッダ 0x0104-0x0133 のロゴ (48x8ピクセル、48バイト) を
+// BootROMと同様に2倍に拡大し、画面上端から最終位置までスクロールさせる。
+// 最終位置に到達したフレームで起動音（チャンネル1の2音）を鳴らす。
+//
+// ロゴデータの配置:
+//   前半24バイト: 上4行、後半24バイト: 下4行
+//   2バイトで4x4ピクセルのブロック（各ニブルが1行、bit3が左端）
+
+use alloc::boxed::Box;
+use crate::apu::Apu;
+use crate::memory_map::io_registers::*;
+use crate::ppu::tiles::ColorConverter;
+
+/// ロゴの幅 (ピクセル)
+pub const LOGO_WIDTH: usize = 48;
+/// ロゴの高さ (ピクセル)
+pub const LOGO_HEIGHT: usize = 8;
+/// 表示時の拡大率 (BootROMは各ピクセルを2x2で描画)
+const LOGO_SCALE: usize = 2;
+/// ロゴ左端の画面X座標（中央揃え: (160 - 96) / 2）
+pub const LOGO_X: usize = (160 - LOGO_WIDTH * LOGO_SCALE) / 2;
+/// ロゴ上端の最終画面Y座標（タイルマップ8行目 = SCY 0 のとき）
+pub const LOGO_FINAL_Y: i32 = 64;
+/// 起動音の2音目までのフレーム数
+const CHIME_SECOND_NOTE_DELAY: u32 = 6;
+
+/// カートリッジヘッダの任天堂ロゴデータ
+pub const NINTENDO_LOGO: [u8; 48] = [
+    0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83,
+    0x00, 0x0C, 0x00, 0x0D, 0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E,
+    0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99, 0xBB, 0xBB, 0x67, 0x63,
+    0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
+];
+
+/// ロゴデータを48x8のピクセル配列にデコード（true=描画ピクセル）
+pub fn decode_logo(data: &[u8; 48]) -> [[bool; LOGO_WIDTH]; LOGO_HEIGHT] {
+    let mut pixels = [[false; LOGO_WIDTH]; LOGO_HEIGHT];
+    for (i, &byte) in data.iter().enumerate() {
+        let half = i / 24;        // 0=上半分, 1=下半分
+        let block = (i % 24) / 2; // 横方向のブロック番号 (0-11)
+        let row = half * 4 + (i % 2) * 2;
+        for (offset, nibble) in [byte >> 4, byte & 0x0F].into_iter().enumerate() {
+            for bit in 0..4 {
+                pixels[row + offset][block * 4 + bit] = nibble & (0x08 >> bit) != 0;
+            }
+        }
+    }
+    pixels
+}
+
+/// 起動アニメーションの設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BootAnimationConfig {
+    /// 開始時のスクロール量（BootROMのSCY初期値 0x64）
+    pub start_offset: u8,
+    /// 1ピクセルスクロールするごとのフレーム数
+    pub frames_per_step: u32,
+    /// 最終位置に到達してからのフレーム数（起動音を含む）
+    pub hold_frames: u32,
+}
+
+impl BootAnimationConfig {
+    /// スクロール部分のフレーム数
+    pub fn scroll_frames(&self) -> u32 {
+        self.start_offset as u32 * self.frames_per_step.max(1)
+    }
+
+    /// アニメーション全体のフレーム数（最後のフレームは必ず最終位置）
+    pub fn total_frames(&self) -> u32 {
+        self.scroll_frames() + self.hold_frames.max(1)
+    }
+
+    /// 指定フレームでのロゴ上端の画面Y座標
+    pub fn logo_y(&self, frame: u32) -> i32 {
+        let steps = (frame / self.frames_per_step.max(1)).min(self.start_offset as u32);
+        LOGO_FINAL_Y - (self.start_offset as i32 - steps as i32)
+    }
+}
+
+impl Default for BootAnimationConfig {
+    fn default() -> Self {
+        Self {
+            start_offset: 0x64,
+            frames_per_step: 2,
+            hold_frames: 60,
+        }
+    }
+}
+
+/// ロゴを指定Y座標に描画したフレームを作成
+///
+/// BGと同様に256ピクセルで折り返し、画面外 (144以上) の行は描画しない
+pub fn render_logo_frame(logo: &[[bool; LOGO_WIDTH]; LOGO_HEIGHT], logo_y: i32) -> Box<[u8; 160 * 144 * 3]> {
+    let (bg_r, bg_g, bg_b) = ColorConverter::dmg_to_rgb888(0);
+    let mut frame = Box::new([0u8; 160 * 144 * 3]);
+    for pixel in frame.chunks_exact_mut(3) {
+        pixel.copy_from_slice(&[bg_r, bg_g, bg_b]);
+    }
+
+    let (r, g, b) = ColorConverter::dmg_to_rgb888(3);
+    for y in 0..LOGO_HEIGHT * LOGO_SCALE {
+        let screen_y = (logo_y + y as i32).rem_euclid(256) as usize;
+        if screen_y >= 144 {
+            continue;
+        }
+        for x in 0..LOGO_WIDTH * LOGO_SCALE {
+            if logo[y / LOGO_SCALE][x / LOGO_SCALE] {
+                let index = (screen_y * 160 + LOGO_X + x) * 3;
+                frame[index..index + 3].copy_from_slice(&[r, g, b]);
+            }
+        }
+    }
+    frame
+}
+
+/// 起動音の1音を鳴らす（BootROMと同じレジスタ設定、second=trueで2音目）
+pub fn play_chime_note(apu: &mut Apu, second: bool) {
+    if !second {
+        apu.write(NR52, 0x80); // APU電源オン
+        apu.write(NR51, 0xF3);
+        apu.write(NR50, 0x77);
+        apu.write(NR11, 0x80); // デューティ50%
+        apu.write(NR12, 0xF3); // 音量15、減衰
+    }
+    apu.write(NR13, if second { 0xC1 } else { 0x83 });
+    apu.write(NR14, 0x87); // トリガー
+}
+
+/// 起動アニメーションの各フレームで起動音を鳴らすタイミングかどうか
+pub fn chime_note_at(config: &BootAnimationConfig, frame: u32) -> Option<bool> {
+    let arrival = config.scroll_frames();
+    if frame == arrival {
+        Some(false)
+    } else if frame == arrival + CHIME_SECOND_NOTE_DELAY {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_logo() {
+        let logo = decode_logo(&NINTENDO_LOGO);
+
+        // 先頭ブロック (0xCE, 0xED): 1100 / 1110 / 1110 / 1101
+        assert_eq!(logo[0][..4], [true, true, false, false]);
+        assert_eq!(logo[1][..4], [true, true, true, false]);
+        assert_eq!(logo[3][..4], [true, true, false, true]);
+
+        // 下半分の先頭ブロック (0xDC, 0xCC): 1101 / 1100 / 1100 / 1100
+        assert_eq!(logo[4][..4], [true, true, false, true]);
+        assert_eq!(logo[7][..4], [true, true, false, false]);
+    }
+
+    #[test]
+    fn test_logo_y_scroll() {
+        let config = BootAnimationConfig::default();
+        assert_eq!(config.logo_y(0), LOGO_FINAL_Y - 0x64);
+        assert_eq!(config.logo_y(2), LOGO_FINAL_Y - 0x63);
+        assert_eq!(config.logo_y(config.scroll_frames()), LOGO_FINAL_Y);
+        assert_eq!(config.logo_y(config.total_frames() - 1), LOGO_FINAL_Y);
+    }
+
+    #[test]
+    fn test_chime_timing() {
+        let config = BootAnimationConfig::default();
+        assert_eq!(chime_note_at(&config, config.scroll_frames() - 1), None);
+        assert_eq!(chime_note_at(&config, config.scroll_frames()), Some(false));
+        assert_eq!(chime_note_at(&config, config.scroll_frames() + CHIME_SECOND_NOTE_DELAY), Some(true));
+    }
+}
@@ -28,6 +28,9 @@
 //   0x1D: MBC5+RUMBLE+RAM
 //   0x1E: MBC5+RUMBLE+RAM+BATTERY
 
+use alloc::{boxed::Box, format, string::String, string::ToString, sync::Arc, vec, vec::Vec};
+use crate::error::LoadError;
+
 /// カートリッジタイプ
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CartridgeType {
@@ -129,6 +132,9 @@ fn rom_banks_from_byte(byte: u8) -> usize {
         0x06 => 128, // 2MB
         0x07 => 256, // 4MB
         0x08 => 512, // 8MB
+        0x52 => 72,  // 1.1MB
+        0x53 => 80,  // 1.2MB
+        0x54 => 96,  // 1.5MB
         _ => 2,
     }
 }
@@ -146,8 +152,30 @@ fn ram_size_from_byte(byte: u8) -> usize {
     }
 }
 
+/// 外部RAMアクセスの失敗理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RamError {
+    /// RAMが有効化されていない (0x0000-0x1FFFに0x0Aが書かれていない)
+    Disabled,
+    /// 現在のバンク/アドレスがRAMサイズを超えている
+    OutOfRange,
+    /// カートリッジにRAMが搭載されていない
+    NoRam,
+}
+
+impl core::fmt::Display for RamError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let message = match self {
+            RamError::Disabled => "外部RAMが無効です",
+            RamError::OutOfRange => "外部RAMの範囲外です",
+            RamError::NoRam => "外部RAMが搭載されていません",
+        };
+        write!(f, "{}", message)
+    }
+}
+
 /// カートリッジヘッダ情報
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CartridgeHeader {
     pub title: String,
     pub cartridge_type: CartridgeType,
@@ -227,10 +255,28 @@ impl RtcRegisters {
     }
 }
 
+/// カートリッジのROM/RAMマッピング (MBC) のインターフェース
+///
+/// 組み込みのMBC1/2/3/5は`Cartridge`自身がこのトレイトを実装する。
+/// 独自マッパー (MBC6/MBC7や自作ハードウェア等) は`Cartridge::set_mapper`で設定すると、
+/// 組み込みのMBC処理の代わりにバスからのアクセスを受け取る。
+pub trait Mapper {
+    /// ROM領域の読み取り (0x0000-0x7FFF)
+    fn read_rom(&self, addr: u16) -> u8;
+    /// ROM領域への書き込み (マッパーレジスタ操作)
+    fn write_rom(&mut self, addr: u16, value: u8);
+    /// 外部RAM読み取り (0xA000-0xBFFF)
+    fn read_ram(&self, addr: u16) -> u8;
+    /// 外部RAM書き込み (0xA000-0xBFFF)
+    fn write_ram(&mut self, addr: u16, value: u8);
+    /// 指定CPUサイクル分進める (タイマー等を持つマッパー用)
+    fn tick(&mut self, _cycles: u32) {}
+}
+
 /// カートリッジ
 pub struct Cartridge {
-    /// ROMデータ
-    rom: Vec<u8>,
+    /// ROMデータ（実行中は不変なのでセーブステート間で共有する）
+    rom: Arc<[u8]>,
     /// 外部RAM
     ram: Vec<u8>,
     /// ヘッダ情報
@@ -257,6 +303,32 @@ pub struct Cartridge {
     rtc_mapped: bool,
     /// RTC秒カウンタ (CPUサイクル→秒への変換)
     rtc_cycle_counter: u32,
+
+    /// 独自マッパー (設定時は組み込みのMBC処理より優先)
+    mapper: Option<Box<dyn Mapper>>,
+}
+
+/// 状態の複製（セーブステート用）
+///
+/// ROMは共有し、RAM・バンクレジスタ・RTCを複製する。独自マッパーは複製できないため含まない
+impl Clone for Cartridge {
+    fn clone(&self) -> Self {
+        Self {
+            rom: Arc::clone(&self.rom),
+            ram: self.ram.clone(),
+            header: self.header.clone(),
+            ram_enabled: self.ram_enabled,
+            rom_bank: self.rom_bank,
+            ram_bank: self.ram_bank,
+            banking_mode: self.banking_mode,
+            rtc: self.rtc.clone(),
+            rtc_latched: self.rtc_latched.clone(),
+            rtc_latch_pending: self.rtc_latch_pending,
+            rtc_mapped: self.rtc_mapped,
+            rtc_cycle_counter: self.rtc_cycle_counter,
+            mapper: None,
+        }
+    }
 }
 
 /// CPUサイクル→1秒 (4,194,304サイクル)
@@ -264,12 +336,10 @@ const CYCLES_PER_SECOND: u32 = 4_194_304;
 
 impl Cartridge {
     /// ROMデータからカートリッジを作成
-    pub fn new(rom_data: Vec<u8>) -> Result<Self, String> {
-        if rom_data.len() < 0x150 {
-            return Err("ROMデータが小さすぎます（ヘッダが不足）".to_string());
-        }
-
-        let header = Self::parse_header(&rom_data);
+    ///
+    /// 未知のカートリッジタイプはROM ONLYとして読み込む（独自マッパーは`set_mapper`で後から設定できる）
+    pub fn new(rom_data: Vec<u8>) -> Result<Self, LoadError> {
+        let header = Self::validate_header(&rom_data)?;
         let ram_size = header.ram_size;
 
         // MBC種別に応じたRAMサイズ決定
@@ -285,7 +355,7 @@ impl Cartridge {
         };
 
         Ok(Self {
-            rom: rom_data,
+            rom: rom_data.into(),
             ram: vec![0; actual_ram_size],
             header,
             ram_enabled: false,
@@ -297,9 +367,28 @@ impl Cartridge {
             rtc_latch_pending: false,
             rtc_mapped: false,
             rtc_cycle_counter: 0,
+            mapper: None,
         })
     }
 
+    /// 組み込みのMBCで扱えるカートリッジのみ作成
+    ///
+    /// 未知のカートリッジタイプは`LoadError::UnsupportedMapper`を返す
+    pub fn new_builtin(rom_data: Vec<u8>) -> Result<Self, LoadError> {
+        let cart = Self::new(rom_data)?;
+        if let CartridgeType::Unknown(byte) = cart.header.cartridge_type {
+            return Err(LoadError::UnsupportedMapper(byte));
+        }
+        Ok(cart)
+    }
+
+    /// 独自マッパー付きでカートリッジを作成（MBC6/MBC7等の組み込みにないカートリッジ用）
+    pub fn new_with_mapper(rom_data: Vec<u8>, mapper: Box<dyn Mapper>) -> Result<Self, LoadError> {
+        let mut cart = Self::new(rom_data)?;
+        cart.set_mapper(mapper);
+        Ok(cart)
+    }
+
     /// ROM ONLYカートリッジを作成（テスト用）
     pub fn new_rom_only(rom_data: Vec<u8>) -> Self {
         let len = rom_data.len();
@@ -309,7 +398,7 @@ impl Cartridge {
         }
 
         Self {
-            rom: padded,
+            rom: padded.into(),
             ram: vec![0; 0],
             header: CartridgeHeader {
                 title: "TEST".to_string(),
@@ -326,7 +415,43 @@ impl Cartridge {
             rtc_latch_pending: false,
             rtc_mapped: false,
             rtc_cycle_counter: 0,
+            mapper: None,
+        }
+    }
+
+    /// ヘッダを検証して解析
+    fn validate_header(rom: &[u8]) -> Result<CartridgeHeader, LoadError> {
+        if rom.len() < 0x150 {
+            return Err(LoadError::TooSmall); // ヘッダが不足
+        }
+        if !matches!(rom[0x0148], 0x00..=0x08 | 0x52..=0x54) || rom[0x0149] > 0x05 {
+            return Err(LoadError::BadHeader); // ROM/RAMサイズコードが範囲外
         }
+
+        Ok(Self::parse_header(rom))
+    }
+
+    /// ROMデータのみを差し替える（バンクレジスタ・外部RAM・RTCは保持）
+    ///
+    /// 新しいROMはヘッダを再解析し、同じMBC種別で現在以上のバンク数を持つ場合のみ受け付ける
+    pub fn swap_rom(&mut self, new_rom: Vec<u8>) -> Result<(), String> {
+        let header = Self::validate_header(&new_rom).map_err(|e| e.to_string())?;
+        if header.cartridge_type.mbc_kind() != self.header.cartridge_type.mbc_kind() {
+            return Err(format!(
+                "MBC種別が異なるROMには差し替えできません: {:?} -> {:?}",
+                self.header.cartridge_type, header.cartridge_type
+            ));
+        }
+        if header.rom_banks < self.header.rom_banks {
+            return Err(format!(
+                "ROMバンク数が不足しています: {} < {}",
+                header.rom_banks, self.header.rom_banks
+            ));
+        }
+
+        self.rom = new_rom.into();
+        self.header = header;
+        Ok(())
     }
 
     /// ヘッダを解析
@@ -350,21 +475,45 @@ impl Cartridge {
         }
     }
 
-    /// カートリッジを1 CPUサイクル進める (RTC用)
-    pub fn tick(&mut self) {
+    /// 独自マッパーを設定（以降のROM/RAMアクセスとtickはマッパーに委譲）
+    pub fn set_mapper(&mut self, mapper: Box<dyn Mapper>) {
+        self.mapper = Some(mapper);
+    }
+
+    /// 独自マッパーを解除して組み込みのMBC処理に戻す
+    pub fn clear_mapper(&mut self) -> Option<Box<dyn Mapper>> {
+        self.mapper.take()
+    }
+
+    /// 独自マッパーが設定されているか
+    pub fn has_custom_mapper(&self) -> bool {
+        self.mapper.is_some()
+    }
+
+    /// カートリッジを指定CPUサイクル分進める (RTC用)
+    ///
+    /// 独自マッパーが設定されていればそのまま委譲する。RTCは経過秒数をまとめて進める
+    pub fn tick(&mut self, cycles: u32) {
+        if let Some(mapper) = self.mapper.as_mut() {
+            mapper.tick(cycles);
+            return;
+        }
         if !self.header.cartridge_type.has_timer() {
             return;
         }
 
-        self.rtc_cycle_counter += 1;
-        if self.rtc_cycle_counter >= CYCLES_PER_SECOND {
-            self.rtc_cycle_counter = 0;
+        let total = self.rtc_cycle_counter as u64 + cycles as u64;
+        self.rtc_cycle_counter = (total % CYCLES_PER_SECOND as u64) as u32;
+        for _ in 0..total / CYCLES_PER_SECOND as u64 {
             self.rtc.tick_second();
         }
     }
 
     /// ROM領域の読み取り (0x0000-0x7FFF)
     pub fn read_rom(&self, addr: u16) -> u8 {
+        if let Some(mapper) = &self.mapper {
+            return mapper.read_rom(addr);
+        }
         match self.header.cartridge_type.mbc_kind() {
             MbcKind::None => self.read_rom_none(addr),
             MbcKind::Mbc1 => self.read_rom_mbc1(addr),
@@ -374,8 +523,93 @@ impl Cartridge {
         }
     }
 
+    /// ROM領域から16bit値を読み取り (リトルエンディアン、現在のバンク状態を反映)
+    pub fn read_rom16(&self, addr: u16) -> u16 {
+        let low = self.read_rom(addr) as u16;
+        let high = self.read_rom(addr.wrapping_add(1)) as u16;
+        (high << 8) | low
+    }
+
+    /// 指定バンクの16KB分のROMデータを取得 (MBCのバンク状態に依存しない)
+    pub fn rom_slice(&self, bank: u16) -> Result<&[u8], String> {
+        let start = bank as usize * 0x4000;
+        if start >= self.rom.len() {
+            return Err(format!(
+                "ROMバンク{}は存在しません (ROMサイズ: {}バイト)",
+                bank,
+                self.rom.len()
+            ));
+        }
+        let end = (start + 0x4000).min(self.rom.len());
+        Ok(&self.rom[start..end])
+    }
+
+    /// ROMデータの実際のサイズ（バイト）
+    pub fn rom_size_bytes(&self) -> usize {
+        self.rom.len()
+    }
+
+    /// 外部RAMのサイズ（バイト、MBC2は内蔵512バイト）
+    pub fn ram_size_bytes(&self) -> usize {
+        self.ram.len()
+    }
+
+    /// 外部RAMのバンク数（8KB単位、8KB未満のRAMは1バンク）
+    pub fn ram_bank_count(&self) -> usize {
+        self.ram.len().div_ceil(0x2000)
+    }
+
+    /// 0x4000-0x7FFF窓に現在マップされているROMバンク番号（デバッガ表示用）
+    ///
+    /// MBC1のバンク0→1のリダイレクトやバンク数による折り返しを反映した実効値。
+    /// 独自マッパー設定時は組み込みMBCの状態を返す
+    pub fn current_rom_bank(&self) -> u16 {
+        match self.header.cartridge_type.mbc_kind() {
+            MbcKind::None => 1,
+            MbcKind::Mbc1 => self.effective_rom_bank_mbc1() as u16,
+            MbcKind::Mbc2 | MbcKind::Mbc3 | MbcKind::Mbc5 => {
+                ((self.rom_bank as usize) % self.header.rom_banks) as u16
+            }
+        }
+    }
+
+    /// 0xA000-0xBFFF窓に現在マップされている外部RAMバンク番号（デバッガ表示用）
+    ///
+    /// MBC1のROMバンキングモードでは常に0。MBC3でRTCレジスタがマップされているかは
+    /// rtc_mappedで確認する
+    pub fn current_ram_bank(&self) -> u8 {
+        (self.ram_offset(0xA000) / 0x2000) as u8
+    }
+
+    /// MBC3で0xA000-0xBFFFにRTCレジスタがマップされているか
+    pub fn rtc_mapped(&self) -> bool {
+        self.header.cartridge_type.mbc_kind() == MbcKind::Mbc3 && self.rtc_mapped
+    }
+
+    /// ヘッダ記載のROMサイズと実際のROMデータ長 (宣言値, 実サイズ)
+    ///
+    /// 一致しない場合は不完全なダンプや誤ったヘッダの可能性がある
+    pub fn declared_vs_actual_rom(&self) -> (usize, usize) {
+        (self.header.rom_banks * 0x4000, self.rom.len())
+    }
+
+    /// エントリポイント (0x0100-0x0103) の4バイトを取得
+    pub fn entry_point_bytes(&self) -> [u8; 4] {
+        let mut bytes = [0xFF; 4];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            if let Some(&value) = self.rom.get(0x0100 + i) {
+                *byte = value;
+            }
+        }
+        bytes
+    }
+
     /// ROM領域への書き込み (MBCレジスタ操作)
     pub fn write_rom(&mut self, addr: u16, value: u8) {
+        if let Some(mapper) = self.mapper.as_mut() {
+            mapper.write_rom(addr, value);
+            return;
+        }
         match self.header.cartridge_type.mbc_kind() {
             MbcKind::None => {} // ROM ONLYは書き込み不可
             MbcKind::Mbc1 => self.write_rom_mbc1(addr, value),
@@ -387,6 +621,9 @@ impl Cartridge {
 
     /// 外部RAM読み取り (0xA000-0xBFFF)
     pub fn read_ram(&self, addr: u16) -> u8 {
+        if let Some(mapper) = &self.mapper {
+            return mapper.read_ram(addr);
+        }
         match self.header.cartridge_type.mbc_kind() {
             MbcKind::None => 0xFF,
             MbcKind::Mbc1 => self.read_ram_mbc1(addr),
@@ -398,6 +635,10 @@ impl Cartridge {
 
     /// 外部RAM書き込み (0xA000-0xBFFF)
     pub fn write_ram(&mut self, addr: u16, value: u8) {
+        if let Some(mapper) = self.mapper.as_mut() {
+            mapper.write_ram(addr, value);
+            return;
+        }
         match self.header.cartridge_type.mbc_kind() {
             MbcKind::None => {}
             MbcKind::Mbc1 => self.write_ram_mbc1(addr, value),
@@ -407,6 +648,52 @@ impl Cartridge {
         }
     }
 
+    /// 外部RAM書き込み (失敗理由を返す版、ツール・検証用)
+    pub fn try_write_ram(&mut self, addr: u16, value: u8) -> Result<(), RamError> {
+        self.check_ram_access(addr)?;
+        self.write_ram(addr, value);
+        Ok(())
+    }
+
+    /// 現在のMBC状態で外部RAMにアクセスできるか確認
+    fn check_ram_access(&self, addr: u16) -> Result<(), RamError> {
+        let kind = self.header.cartridge_type.mbc_kind();
+        if !(0xA000..=0xBFFF).contains(&addr) {
+            return Err(RamError::OutOfRange);
+        }
+
+        // 独自マッパーのRAM状態は把握できないため委譲先に任せる
+        if self.mapper.is_some() {
+            return Ok(());
+        }
+
+        // MBC3のRTCレジスタはRAMが無くてもアクセス可能
+        let rtc = kind == MbcKind::Mbc3 && self.rtc_mapped;
+        if kind == MbcKind::None || (self.ram.is_empty() && !rtc) {
+            return Err(RamError::NoRam);
+        }
+        if !self.ram_enabled {
+            return Err(RamError::Disabled);
+        }
+        if !rtc && self.ram_offset(addr) >= self.ram.len() {
+            return Err(RamError::OutOfRange);
+        }
+        Ok(())
+    }
+
+    /// 現在のバンク状態での外部RAMオフセット
+    fn ram_offset(&self, addr: u16) -> usize {
+        let relative = addr as usize - 0xA000;
+        let bank = match self.header.cartridge_type.mbc_kind() {
+            MbcKind::Mbc1 if self.banking_mode == Mbc1Mode::Ram => self.ram_bank as usize,
+            MbcKind::Mbc2 => return relative & 0x01FF, // 512×4ビット、下位9ビットでアクセス
+            MbcKind::Mbc3 => (self.ram_bank as usize) & 0x03,
+            MbcKind::Mbc5 => self.ram_bank as usize,
+            _ => 0,
+        };
+        bank * 0x2000 + relative
+    }
+
     // ===== ROM ONLY =====
 
     fn read_rom_none(&self, addr: u16) -> u8 {
@@ -418,8 +705,9 @@ impl Cartridge {
     fn read_rom_mbc1(&self, addr: u16) -> u8 {
         match addr {
             0x0000..=0x3FFF => {
+                // 上位ビットもROMサイズで折り返す（大容量RAM・小容量ROMではバンク0のまま）
                 let bank = if self.banking_mode == Mbc1Mode::Ram {
-                    (self.ram_bank as usize) << 5
+                    ((self.ram_bank as usize) << 5) % self.header.rom_banks
                 } else {
                     0
                 };
@@ -462,12 +750,7 @@ impl Cartridge {
         if !self.ram_enabled || self.ram.is_empty() {
             return 0xFF;
         }
-        let bank = if self.banking_mode == Mbc1Mode::Ram {
-            self.ram_bank as usize
-        } else {
-            0
-        };
-        let offset = bank * 0x2000 + (addr as usize - 0xA000);
+        let offset = self.ram_offset(addr);
         self.ram.get(offset).copied().unwrap_or(0xFF)
     }
 
@@ -475,19 +758,18 @@ impl Cartridge {
         if !self.ram_enabled || self.ram.is_empty() {
             return;
         }
-        let bank = if self.banking_mode == Mbc1Mode::Ram {
-            self.ram_bank as usize
-        } else {
-            0
-        };
-        let offset = bank * 0x2000 + (addr as usize - 0xA000);
+        let offset = self.ram_offset(addr);
         if offset < self.ram.len() {
             self.ram[offset] = value;
         }
     }
 
+    /// 0x4000-0x7FFF窓の実効バンク番号
+    /// 下位5bitが0のバンク (0x00/0x20/0x40/0x60) は選択できず+1される
     fn effective_rom_bank_mbc1(&self) -> usize {
-        let bank = (self.ram_bank as usize) << 5 | (self.rom_bank as usize);
+        let low = (self.rom_bank as usize) & 0x1F;
+        let low = if low == 0 { 1 } else { low };
+        let bank = (self.ram_bank as usize) << 5 | low;
         bank % self.header.rom_banks
     }
 
@@ -508,19 +790,17 @@ impl Cartridge {
     }
 
     fn write_rom_mbc2(&mut self, addr: u16, value: u8) {
-        match addr {
-            // RAM有効/無効 — アドレスのbit8が0
-            0x0000..=0x3FFF => {
-                if addr & 0x0100 == 0 {
-                    // RAM Enable/Disable
-                    self.ram_enabled = (value & 0x0F) == 0x0A;
-                } else {
-                    // ROM Bank Number (下位4ビット)
-                    let bank = value & 0x0F;
-                    self.rom_bank = if bank == 0 { 1 } else { bank as u16 };
-                }
-            }
-            _ => {}
+        // 0x0000-0x3FFFのみ有効: アドレスのbit8でRAM有効/ROMバンクを切り替え
+        if addr > 0x3FFF {
+            return;
+        }
+        if addr & 0x0100 == 0 {
+            // RAM Enable/Disable
+            self.ram_enabled = (value & 0x0F) == 0x0A;
+        } else {
+            // ROM Bank Number (下位4ビット)
+            let bank = value & 0x0F;
+            self.rom_bank = if bank == 0 { 1 } else { bank as u16 };
         }
     }
 
@@ -529,7 +809,7 @@ impl Cartridge {
             return 0xFF;
         }
         // MBC2 RAM: 512×4ビット、アドレスの下位9ビットでアクセス
-        let offset = (addr as usize - 0xA000) & 0x01FF;
+        let offset = self.ram_offset(addr);
         if offset < self.ram.len() {
             self.ram[offset] | 0xF0 // 上位4ビットは常に1
         } else {
@@ -541,7 +821,7 @@ impl Cartridge {
         if !self.ram_enabled || self.ram.is_empty() {
             return;
         }
-        let offset = (addr as usize - 0xA000) & 0x01FF;
+        let offset = self.ram_offset(addr);
         if offset < self.ram.len() {
             self.ram[offset] = value & 0x0F; // 下位4ビットのみ
         }
@@ -577,7 +857,7 @@ impl Cartridge {
             // RAMバンク番号 / RTCレジスタ選択
             0x4000..=0x5FFF => {
                 self.ram_bank = value;
-                self.rtc_mapped = value >= 0x08 && value <= 0x0C;
+                self.rtc_mapped = (0x08..=0x0C).contains(&value);
             }
             // RTCラッチ
             0x6000..=0x7FFF => {
@@ -616,8 +896,7 @@ impl Cartridge {
         if self.ram.is_empty() {
             return 0xFF;
         }
-        let bank = (self.ram_bank as usize) & 0x03;
-        let offset = bank * 0x2000 + (addr as usize - 0xA000);
+        let offset = self.ram_offset(addr);
         self.ram.get(offset).copied().unwrap_or(0xFF)
     }
 
@@ -643,8 +922,7 @@ impl Cartridge {
         if self.ram.is_empty() {
             return;
         }
-        let bank = (self.ram_bank as usize) & 0x03;
-        let offset = bank * 0x2000 + (addr as usize - 0xA000);
+        let offset = self.ram_offset(addr);
         if offset < self.ram.len() {
             self.ram[offset] = value;
         }
@@ -692,8 +970,7 @@ impl Cartridge {
         if !self.ram_enabled || self.ram.is_empty() {
             return 0xFF;
         }
-        let bank = self.ram_bank as usize;
-        let offset = bank * 0x2000 + (addr as usize - 0xA000);
+        let offset = self.ram_offset(addr);
         self.ram.get(offset).copied().unwrap_or(0xFF)
     }
 
@@ -701,14 +978,36 @@ impl Cartridge {
         if !self.ram_enabled || self.ram.is_empty() {
             return;
         }
-        let bank = self.ram_bank as usize;
-        let offset = bank * 0x2000 + (addr as usize - 0xA000);
+        let offset = self.ram_offset(addr);
         if offset < self.ram.len() {
             self.ram[offset] = value;
         }
     }
 }
 
+/// 組み込みのMBC処理をMapperとして公開
+impl Mapper for Cartridge {
+    fn read_rom(&self, addr: u16) -> u8 {
+        Cartridge::read_rom(self, addr)
+    }
+
+    fn write_rom(&mut self, addr: u16, value: u8) {
+        Cartridge::write_rom(self, addr, value)
+    }
+
+    fn read_ram(&self, addr: u16) -> u8 {
+        Cartridge::read_ram(self, addr)
+    }
+
+    fn write_ram(&mut self, addr: u16, value: u8) {
+        Cartridge::write_ram(self, addr, value)
+    }
+
+    fn tick(&mut self, cycles: u32) {
+        Cartridge::tick(self, cycles)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -759,6 +1058,37 @@ mod tests {
         assert_eq!(cart.header.rom_banks, 2);
     }
 
+    #[test]
+    fn test_rom_slice() {
+        // 64KB ROM (4バンク)
+        let mut rom = create_test_rom_with_ram(0x10000, 0x01, 0x01, 0x00);
+        rom[0x3FFF] = 0xAA;
+        rom[0x8000] = 0x22;
+        let cart = Cartridge::new(rom).unwrap();
+
+        let bank0 = cart.rom_slice(0).unwrap();
+        assert_eq!(bank0.len(), 0x4000);
+        assert_eq!(bank0[0x0147], 0x01);
+        assert_eq!(bank0[0x3FFF], 0xAA);
+
+        let bank2 = cart.rom_slice(2).unwrap();
+        assert_eq!(bank2.len(), 0x4000);
+        assert_eq!(bank2[0], 0x22);
+
+        // 存在しないバンクはエラー
+        assert!(cart.rom_slice(4).is_err());
+    }
+
+    #[test]
+    fn test_entry_point_and_read_rom16() {
+        let mut rom = create_test_rom(0x8000, 0x00);
+        rom[0x0100..0x0104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // NOP; JP 0x0150
+        let cart = Cartridge::new(rom).unwrap();
+
+        assert_eq!(cart.entry_point_bytes(), [0x00, 0xC3, 0x50, 0x01]);
+        assert_eq!(cart.read_rom16(0x0102), 0x0150);
+    }
+
     // ===== MBC1 テスト =====
 
     #[test]
@@ -786,6 +1116,39 @@ mod tests {
         assert_eq!(cart.read_rom(0x4000), 0x33);
     }
 
+    #[test]
+    fn test_swap_rom_preserves_banking_and_ram() {
+        // 64KB ROM (4バンク) + 8KB RAM
+        let mut rom = create_test_rom_with_ram(0x10000, 0x03, 0x01, 0x02);
+        rom[0xC000] = 0x33; // Bank 3
+        let mut cart = Cartridge::new(rom).unwrap();
+        cart.write_rom(0x2000, 0x03);
+        cart.write_rom(0x0000, 0x0A); // RAM有効
+        cart.write_ram(0xA000, 0x5A);
+        assert_eq!(cart.read_rom(0x4000), 0x33);
+
+        // 128KB ROM (8バンク) に差し替え
+        let mut larger = create_test_rom_with_ram(0x20000, 0x03, 0x02, 0x02);
+        larger[0x0134..0x0138].copy_from_slice(b"SWAP");
+        larger[0xC000] = 0xB3; // Bank 3
+        larger[0x1C000] = 0xB7; // Bank 7
+        cart.swap_rom(larger).unwrap();
+
+        // 選択中のバンク3が新しいROMから読める。RAMと有効状態も保持
+        assert_eq!(cart.header.title, "SWAP");
+        assert_eq!(cart.header.rom_banks, 8);
+        assert_eq!(cart.read_rom(0x4000), 0xB3);
+        assert_eq!(cart.read_ram(0xA000), 0x5A);
+        cart.write_rom(0x2000, 0x07);
+        assert_eq!(cart.read_rom(0x4000), 0xB7);
+
+        // バンク数が減るROM・MBC種別の異なるROM・不正なROMは拒否し、状態は変わらない
+        assert!(cart.swap_rom(create_test_rom_with_ram(0x10000, 0x03, 0x01, 0x02)).is_err());
+        assert!(cart.swap_rom(create_test_rom_with_ram(0x20000, 0x19, 0x02, 0x02)).is_err());
+        assert!(cart.swap_rom(vec![0; 0x100]).is_err());
+        assert_eq!(cart.read_rom(0x4000), 0xB7);
+    }
+
     #[test]
     fn test_mbc1_bank0_redirect() {
         let rom = create_test_rom(0x8000, 0x01);
@@ -794,6 +1157,97 @@ mod tests {
         assert_eq!(cart.rom_bank, 1); // バンク1にリダイレクト
     }
 
+    #[test]
+    fn test_current_banks_mbc1() {
+        let mut cart = Cartridge::new(create_mbc1_2mb_rom()).unwrap();
+        assert_eq!(cart.current_rom_bank(), 1);
+        assert_eq!(cart.current_ram_bank(), 0);
+        assert!(!cart.rtc_mapped());
+
+        // バンク0指定は1にリダイレクト
+        cart.write_rom(0x2000, 0x00);
+        assert_eq!(cart.current_rom_bank(), 1);
+
+        // 上位2bitと組み合わせ、0x20は0x21として報告
+        cart.write_rom(0x4000, 0x01);
+        assert_eq!(cart.current_rom_bank(), 0x21);
+        cart.write_rom(0x2000, 0x05);
+        assert_eq!(cart.current_rom_bank(), 0x25);
+
+        // ROMバンキングモードではRAMバンクは0、RAMバンキングモードで上位ビットがRAMバンクになる
+        assert_eq!(cart.current_ram_bank(), 0);
+        cart.write_rom(0x6000, 0x01);
+        assert_eq!(cart.current_ram_bank(), 1);
+
+        // バンク数を超える指定は折り返す (4バンクROMでバンク5→1)
+        let mut rom = create_test_rom(0x10000, 0x01);
+        rom[0x0148] = 0x01;
+        let mut small = Cartridge::new(rom).unwrap();
+        small.write_rom(0x2000, 0x05);
+        assert_eq!(small.current_rom_bank(), 1);
+    }
+
+    /// 2MB ROM (128バンク) を作成し、各バンク先頭にバンク番号を配置
+    fn create_mbc1_2mb_rom() -> Vec<u8> {
+        let mut rom = create_test_rom_with_ram(0x200000, 0x01, 0x06, 0x00);
+        for bank in 1..128 {
+            rom[bank * 0x4000] = bank as u8;
+        }
+        rom
+    }
+
+    #[test]
+    fn test_mbc1_high_bank_redirect() {
+        let mut cart = Cartridge::new(create_mbc1_2mb_rom()).unwrap();
+
+        // 0x20/0x40/0x60 を選択すると 0x21/0x41/0x61 が読める
+        for (upper, expected) in [(1u8, 0x21u8), (2, 0x41), (3, 0x61)] {
+            cart.write_rom(0x2000, 0x00);
+            cart.write_rom(0x4000, upper);
+            assert_eq!(cart.read_rom(0x4000), expected);
+        }
+
+        // 対照: 下位5bitが0でなければそのまま（リダイレクトされない）
+        cart.write_rom(0x4000, 0x01);
+        cart.write_rom(0x2000, 0x02);
+        assert_eq!(cart.read_rom(0x4000), 0x22);
+
+        // モード0/1どちらでも、下位5bitが0なら上位ビットごとに+1される
+        // (0x2000への0x20/0x40/0x60も下位5bitにマスクされて0になる)
+        for mode in [0x00u8, 0x01] {
+            cart.write_rom(0x6000, mode);
+            for upper in 0..4u8 {
+                for low in [0x00u8, 0x20, 0x40, 0x60] {
+                    cart.write_rom(0x4000, upper);
+                    cart.write_rom(0x2000, low);
+                    assert_eq!(
+                        cart.read_rom(0x4000),
+                        upper * 0x20 + 1,
+                        "mode={} upper={} low=0x{:02X}",
+                        mode,
+                        upper,
+                        low
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mbc1_high_bank_in_bank0_window() {
+        let mut cart = Cartridge::new(create_mbc1_2mb_rom()).unwrap();
+
+        // モード1では0x0000-0x3FFF窓にバンク0x20が直接マップされる（+1されない）
+        cart.write_rom(0x6000, 0x01);
+        cart.write_rom(0x4000, 0x01);
+        assert_eq!(cart.read_rom(0x0000), 0x20);
+        assert_eq!(cart.read_rom(0x4000), 0x21);
+
+        // モード0ではバンク0に戻る
+        cart.write_rom(0x6000, 0x00);
+        assert_eq!(cart.read_rom(0x0000), 0x00);
+    }
+
     #[test]
     fn test_mbc1_ram() {
         let mut rom = create_test_rom(0x8000, 0x02); // MBC1+RAM
@@ -814,10 +1268,100 @@ mod tests {
         assert_eq!(cart.read_ram(0xA000), 0xFF);
     }
 
+    #[test]
+    fn test_mbc1_32kb_ram_banking() {
+        // 32KB ROM + 32KB RAM (4バンク)
+        let mut rom = create_test_rom_with_ram(0x8000, 0x03, 0x00, 0x03);
+        rom[0x4000] = 0x01;
+        let mut cart = Cartridge::new(rom).unwrap();
+        cart.write_rom(0x0000, 0x0A);
+
+        // モード1: 0x4000-0x5FFFの値がRAMバンクを選択する
+        cart.write_rom(0x6000, 0x01);
+        for bank in 0..4u8 {
+            cart.write_rom(0x4000, bank);
+            cart.write_ram(0xA000, 0x10 + bank);
+            cart.write_ram(0xBFFF, 0x20 + bank);
+        }
+        for bank in 0..4u8 {
+            cart.write_rom(0x4000, bank);
+            assert_eq!(cart.current_ram_bank(), bank);
+            assert_eq!(cart.read_ram(0xA000), 0x10 + bank);
+            assert_eq!(cart.read_ram(0xBFFF), 0x20 + bank);
+
+            // 小容量ROMでは上位ビットがROMバンクに影響しない
+            assert_eq!(cart.read_rom(0x0000), 0x00);
+            assert_eq!(cart.read_rom(0x4000), 0x01);
+        }
+
+        // モード0: RAMはバンク0に固定される
+        cart.write_rom(0x4000, 0x03);
+        cart.write_rom(0x6000, 0x00);
+        assert_eq!(cart.current_ram_bank(), 0);
+        assert_eq!(cart.read_ram(0xA000), 0x10);
+        cart.write_ram(0xA000, 0x99);
+
+        cart.write_rom(0x6000, 0x01);
+        assert_eq!(cart.read_ram(0xA000), 0x13);
+        cart.write_rom(0x4000, 0x00);
+        assert_eq!(cart.read_ram(0xA000), 0x99);
+    }
+
     #[test]
     fn test_rom_too_small() {
         let rom = vec![0u8; 0x100]; // ヘッダが不足
-        assert!(Cartridge::new(rom).is_err());
+        assert!(matches!(Cartridge::new(rom), Err(LoadError::TooSmall)));
+    }
+
+    #[test]
+    fn test_rom_size_introspection() {
+        // ヘッダは64KBだが実データは32KB
+        let rom = create_test_rom_with_ram(0x8000, 0x03, 0x01, 0x03);
+        let cart = Cartridge::new(rom).unwrap();
+        assert_eq!(cart.declared_vs_actual_rom(), (0x10000, 0x8000));
+        assert_eq!(cart.rom_size_bytes(), 0x8000);
+        assert_eq!(cart.ram_size_bytes(), 32 * 1024);
+        assert_eq!(cart.ram_bank_count(), 4);
+
+        // 一致するROM、RAMなし
+        let cart = Cartridge::new(create_test_rom(0x8000, 0x00)).unwrap();
+        assert_eq!(cart.declared_vs_actual_rom(), (0x8000, 0x8000));
+        assert_eq!(cart.ram_bank_count(), 0);
+
+        // MBC2の内蔵RAMは1バンク扱い
+        let cart = Cartridge::new(create_test_rom(0x8000, 0x06)).unwrap();
+        assert_eq!(cart.ram_size_bytes(), 512);
+        assert_eq!(cart.ram_bank_count(), 1);
+    }
+
+    #[test]
+    fn test_rom_header_errors() {
+        // 未対応のカートリッジタイプ (0xFC: POCKET CAMERA) は組み込みMBC指定時のみエラー
+        let rom = create_test_rom(0x8000, 0xFC);
+        assert!(matches!(Cartridge::new_builtin(rom.clone()), Err(LoadError::UnsupportedMapper(0xFC))));
+        assert!(Cartridge::new(rom).is_ok());
+
+        // ROMサイズコードが範囲外
+        let rom = create_test_rom_with_ram(0x8000, 0x00, 0x09, 0x00);
+        assert!(matches!(Cartridge::new(rom), Err(LoadError::BadHeader)));
+    }
+
+    #[test]
+    fn test_unknown_types_load_as_rom_only() {
+        // ROM+RAM (0x08/0x09) は従来どおりROM ONLYとして読み込める
+        for cart_type in [0x08, 0x09] {
+            let mut rom = create_test_rom(0x8000, cart_type);
+            rom[0x7FFF] = 0x42;
+            let cart = Cartridge::new(rom).unwrap();
+            assert_eq!(cart.header.cartridge_type, CartridgeType::Unknown(cart_type));
+            assert_eq!(cart.read_rom(0x7FFF), 0x42);
+        }
+
+        // 0x52-0x54のROMサイズコード
+        for (code, banks) in [(0x52, 72), (0x53, 80), (0x54, 96)] {
+            let rom = create_test_rom_with_ram(0x8000, 0x00, code, 0x00);
+            assert_eq!(Cartridge::new(rom).unwrap().header.rom_banks, banks);
+        }
     }
 
     #[test]
@@ -959,6 +1503,15 @@ mod tests {
         // バンク1の読み取り
         cart.write_rom(0x4000, 0x01);
         assert_eq!(cart.read_ram(0xA000), 0x22);
+        assert_eq!(cart.current_ram_bank(), 1);
+        assert!(!cart.rtc_mapped());
+
+        // RTCレジスタ選択
+        cart.write_rom(0x4000, 0x08);
+        assert!(cart.rtc_mapped());
+        cart.write_rom(0x4000, 0x02);
+        assert!(!cart.rtc_mapped());
+        assert_eq!(cart.current_ram_bank(), 2);
     }
 
     #[test]
@@ -993,7 +1546,7 @@ mod tests {
 
         // 1秒分のサイクルを進める
         for _ in 0..CYCLES_PER_SECOND {
-            cart.tick();
+            cart.tick(1);
         }
 
         // ラッチしてRTC値を確認
@@ -1017,7 +1570,7 @@ mod tests {
 
         // サイクルを進めてもRTCは変化しない
         for _ in 0..CYCLES_PER_SECOND * 2 {
-            cart.tick();
+            cart.tick(1);
         }
 
         // ラッチして確認
@@ -1028,6 +1581,74 @@ mod tests {
         assert_eq!(cart.read_ram(0xA000), 0); // 0秒のまま
     }
 
+    /// 日付が変わる直前 (23:59:59) のRTCレジスタを作成
+    fn rtc_before_midnight(days_low: u8, days_high: u8) -> RtcRegisters {
+        RtcRegisters { seconds: 59, minutes: 59, hours: 23, days_low, days_high }
+    }
+
+    #[test]
+    fn test_mbc3_rtc_day_carry_at_256() {
+        // 255日 → 256日: days_lowが0に戻り、days_high bit0 (日カウンタbit8) が立つ
+        let mut rtc = rtc_before_midnight(0xFF, 0x00);
+        rtc.tick_second();
+        assert_eq!((rtc.seconds, rtc.minutes, rtc.hours), (0, 0, 0));
+        assert_eq!(rtc.days_low, 0x00);
+        assert_eq!(rtc.days_high, 0x01);
+        assert_eq!(rtc.day_counter(), 256);
+
+        // 257日目も上位ビットは維持
+        let mut rtc = rtc_before_midnight(0x00, 0x01);
+        rtc.tick_second();
+        assert_eq!(rtc.days_low, 0x01);
+        assert_eq!(rtc.days_high, 0x01);
+    }
+
+    #[test]
+    fn test_mbc3_rtc_day_overflow_at_512() {
+        // 511日 → 512日: 日カウンタは0に戻り、オーバーフロー (bit7) が立つ
+        let mut rtc = rtc_before_midnight(0xFF, 0x01);
+        rtc.tick_second();
+        assert_eq!(rtc.days_low, 0x00);
+        assert_eq!(rtc.days_high, 0x80);
+        assert_eq!(rtc.day_counter(), 0);
+
+        // オーバーフローは以降の日付更新でもクリアされない
+        rtc.hours = 23;
+        rtc.minutes = 59;
+        rtc.seconds = 59;
+        rtc.tick_second();
+        assert_eq!(rtc.days_low, 0x01);
+        assert_eq!(rtc.days_high, 0x80);
+
+        // 256日に到達しても保持される
+        let mut rtc = rtc_before_midnight(0xFF, 0x80);
+        rtc.tick_second();
+        assert_eq!(rtc.days_high, 0x81);
+    }
+
+    #[test]
+    fn test_mbc3_rtc_overflow_cleared_by_write() {
+        let rom = create_test_rom(0x8000, 0x0F);
+        let mut cart = Cartridge::new(rom).unwrap();
+        cart.write_rom(0x0000, 0x0A);
+        cart.rtc = rtc_before_midnight(0xFF, 0x01);
+
+        // 1秒進めて512日に到達させる
+        for _ in 0..CYCLES_PER_SECOND {
+            cart.tick(1);
+        }
+        cart.write_rom(0x6000, 0x00);
+        cart.write_rom(0x6000, 0x01);
+        cart.write_rom(0x4000, 0x0C);
+        assert_eq!(cart.read_ram(0xA000) & 0x81, 0x80);
+
+        // ソフトウェアがdays_highに書き込むまでラッチされる
+        cart.write_ram(0xA000, 0x00);
+        cart.write_rom(0x6000, 0x00);
+        cart.write_rom(0x6000, 0x01);
+        assert_eq!(cart.read_ram(0xA000) & 0x80, 0x00);
+    }
+
     // ===== MBC5 テスト =====
 
     #[test]
@@ -1080,6 +1701,25 @@ mod tests {
         assert_eq!(cart.rom_bank, 0x1FF); // 9ビット
     }
 
+    #[test]
+    fn test_current_banks_mbc5() {
+        let rom = create_test_rom_with_ram(0x80000, 0x1B, 0x04, 0x03); // MBC5+RAM+BATTERY, 512KB ROM, 32KB RAM
+        let mut cart = Cartridge::new(rom).unwrap();
+
+        // MBC5はバンク0をそのまま選択できる
+        cart.write_rom(0x2000, 0x00);
+        assert_eq!(cart.current_rom_bank(), 0);
+        cart.write_rom(0x2000, 0x1F);
+        assert_eq!(cart.current_rom_bank(), 0x1F);
+        // 9bit目を立てても32バンクで折り返す
+        cart.write_rom(0x3000, 0x01);
+        assert_eq!(cart.current_rom_bank(), 0x1F);
+
+        cart.write_rom(0x4000, 0x03);
+        assert_eq!(cart.current_ram_bank(), 3);
+        assert!(!cart.rtc_mapped());
+    }
+
     #[test]
     fn test_mbc5_ram() {
         let rom = create_test_rom_with_ram(0x8000, 0x1A, 0x00, 0x02); // MBC5+RAM, 8KB RAM
@@ -1131,4 +1771,161 @@ mod tests {
         assert!(!CartridgeType::Mbc3.has_timer());
         assert!(!CartridgeType::Mbc1.has_timer());
     }
+
+    // ===== RAMエラー テスト =====
+
+    #[test]
+    fn test_try_write_ram_mbc1() {
+        // RAMなしMBC1
+        let mut cart = Cartridge::new(create_test_rom(0x8000, 0x01)).unwrap();
+        cart.write_rom(0x0000, 0x0A);
+        assert_eq!(cart.try_write_ram(0xA000, 0x42), Err(RamError::NoRam));
+
+        // MBC1+RAM 8KB
+        let rom = create_test_rom_with_ram(0x8000, 0x02, 0x00, 0x02);
+        let mut cart = Cartridge::new(rom).unwrap();
+        assert_eq!(cart.try_write_ram(0xA000, 0x42), Err(RamError::Disabled));
+
+        cart.write_rom(0x0000, 0x0A); // RAM有効化
+        assert_eq!(cart.try_write_ram(0xA000, 0x42), Ok(()));
+        assert_eq!(cart.read_ram(0xA000), 0x42);
+
+        // モード1でRAMバンク1を選択 → 8KBを超える
+        cart.write_rom(0x6000, 0x01);
+        cart.write_rom(0x4000, 0x01);
+        assert_eq!(cart.try_write_ram(0xA000, 0x42), Err(RamError::OutOfRange));
+
+        // 外部RAM領域外のアドレス
+        assert_eq!(cart.try_write_ram(0x9FFF, 0x42), Err(RamError::OutOfRange));
+    }
+
+    #[test]
+    fn test_try_write_ram_mbc3() {
+        let rom = create_test_rom_with_ram(0x8000, 0x13, 0x00, 0x03); // 32KB RAM
+        let mut cart = Cartridge::new(rom).unwrap();
+        assert_eq!(cart.try_write_ram(0xA000, 0x11), Err(RamError::Disabled));
+
+        cart.write_rom(0x0000, 0x0A);
+        cart.write_rom(0x4000, 0x03);
+        assert_eq!(cart.try_write_ram(0xBFFF, 0x11), Ok(()));
+        assert_eq!(cart.read_ram(0xBFFF), 0x11);
+
+        // RAMなしのMBC3+TIMER: RTCレジスタはアクセス可能、RAMはNoRam
+        let rom = create_test_rom_with_ram(0x8000, 0x0F, 0x00, 0x00);
+        let mut cart = Cartridge::new(rom).unwrap();
+        cart.write_rom(0x0000, 0x0A);
+        assert_eq!(cart.try_write_ram(0xA000, 0x11), Err(RamError::NoRam));
+        cart.write_rom(0x4000, 0x08); // RTC秒
+        assert_eq!(cart.try_write_ram(0xA000, 0x11), Ok(()));
+    }
+
+    #[test]
+    fn test_try_write_ram_mbc5() {
+        let rom = create_test_rom_with_ram(0x8000, 0x1A, 0x00, 0x02); // 8KB RAM
+        let mut cart = Cartridge::new(rom).unwrap();
+        assert_eq!(cart.try_write_ram(0xA000, 0x22), Err(RamError::Disabled));
+
+        cart.write_rom(0x0000, 0x0A);
+        assert_eq!(cart.try_write_ram(0xA000, 0x22), Ok(()));
+
+        cart.write_rom(0x4000, 0x02); // 存在しないRAMバンク2
+        assert_eq!(cart.try_write_ram(0xA000, 0x22), Err(RamError::OutOfRange));
+
+        // ROM ONLY
+        let mut cart = Cartridge::new(create_test_rom(0x8000, 0x00)).unwrap();
+        assert_eq!(cart.try_write_ram(0xA000, 0x22), Err(RamError::NoRam));
+    }
+
+    // ===== 独自マッパーテスト =====
+
+    /// ROMは常に固定値を返し、RAMは1バイトだけ持つマッパー
+    struct FixedMapper {
+        value: u8,
+        ram: u8,
+        ticks: u32,
+    }
+
+    impl Mapper for FixedMapper {
+        fn read_rom(&self, _addr: u16) -> u8 {
+            self.value
+        }
+        fn write_rom(&mut self, _addr: u16, value: u8) {
+            self.value = value;
+        }
+        fn read_ram(&self, _addr: u16) -> u8 {
+            self.ram
+        }
+        fn write_ram(&mut self, _addr: u16, value: u8) {
+            self.ram = value;
+        }
+        fn tick(&mut self, cycles: u32) {
+            self.ticks += cycles;
+        }
+    }
+
+    #[test]
+    fn test_custom_mapper_through_bus() {
+        use crate::peripherals::Peripherals;
+
+        let mut cart = Cartridge::new(create_test_rom(0x8000, 0x00)).unwrap();
+        cart.set_mapper(Box::new(FixedMapper { value: 0x42, ram: 0x00, ticks: 0 }));
+        assert!(cart.has_custom_mapper());
+
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        peripherals.write(0xFF50, 0x01);
+        peripherals.load_cartridge(cart);
+
+        // ROM読み取りはマッパー経由
+        assert_eq!(peripherals.read(0x0150), 0x42);
+        assert_eq!(peripherals.read(0x4000), 0x42);
+
+        // ROM書き込みもマッパーのレジスタ操作として届く
+        peripherals.write(0x2000, 0x99);
+        assert_eq!(peripherals.read(0x7FFF), 0x99);
+
+        // 外部RAM
+        peripherals.write(0xA000, 0x5A);
+        assert_eq!(peripherals.read(0xBFFF), 0x5A);
+    }
+
+    #[test]
+    fn test_builtin_mbc_as_mapper() {
+        let mut rom = create_test_rom_with_ram(0x10000, 0x03, 0x01, 0x02);
+        rom[0x4000 * 3] = 0x33;
+        let mut mapper: Box<dyn Mapper> = Box::new(Cartridge::new(rom).unwrap());
+
+        mapper.write_rom(0x2000, 3);
+        assert_eq!(mapper.read_rom(0x4000), 0x33);
+        mapper.write_rom(0x0000, 0x0A);
+        mapper.write_ram(0xA000, 0x77);
+        assert_eq!(mapper.read_ram(0xA000), 0x77);
+    }
+
+    #[test]
+    fn test_mbc7_header_with_custom_mapper() {
+        // MBC7 (0x22) は組み込みにないが、独自マッパーを付けて読み込める
+        let rom = create_test_rom(0x8000, 0x22);
+        assert!(matches!(Cartridge::new_builtin(rom.clone()), Err(LoadError::UnsupportedMapper(0x22))));
+
+        let mut cart = Cartridge::new_with_mapper(rom, Box::new(FixedMapper { value: 0x42, ram: 0x00, ticks: 0 })).unwrap();
+        assert!(cart.has_custom_mapper());
+        assert_eq!(cart.header.cartridge_type, CartridgeType::Unknown(0x22));
+        assert_eq!(cart.read_rom(0x4000), 0x42);
+        cart.write_ram(0xA000, 0x5A);
+        assert_eq!(cart.read_ram(0xA000), 0x5A);
+    }
+
+    #[test]
+    fn test_clear_custom_mapper() {
+        let mut rom = create_test_rom(0x8000, 0x00);
+        rom[0x0150] = 0x12;
+        let mut cart = Cartridge::new(rom).unwrap();
+        cart.set_mapper(Box::new(FixedMapper { value: 0x42, ram: 0x00, ticks: 0 }));
+        cart.tick(1);
+        assert_eq!(cart.read_rom(0x0150), 0x42);
+
+        assert!(cart.clear_mapper().is_some());
+        assert!(!cart.has_custom_mapper());
+        assert_eq!(cart.read_rom(0x0150), 0x12);
+    }
 }
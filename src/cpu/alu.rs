@@ -0,0 +1,58 @@
+// src/cpu/alu.rs
+// 算術命令のハーフキャリー判定
+//
+// ADD/ADC/SUB/SBC/INC/DEC/ADD SP,n などのHフラグは全てここを経由して計算する。
+// bit3（16bit加算はbit11）からの桁上がり/桁借りの判定を1か所にまとめる。
+
+/// 8bit加算のハーフキャリー（bit3→bit4への桁上がり）
+pub(crate) fn half_carry_add(a: u8, b: u8, carry: bool) -> bool {
+    (a & 0x0F) + (b & 0x0F) + carry as u8 > 0x0F
+}
+
+/// 8bit減算のハーフキャリー（bit4からの桁借り）
+pub(crate) fn half_carry_sub(a: u8, b: u8, borrow: bool) -> bool {
+    (a & 0x0F) < (b & 0x0F) + borrow as u8
+}
+
+/// 16bit加算（ADD HL,rr）のハーフキャリー（bit11→bit12への桁上がり）
+pub(crate) fn half_carry_add16(a: u16, b: u16) -> bool {
+    (a & 0x0FFF) + (b & 0x0FFF) > 0x0FFF
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_half_carry_add_exhaustive() {
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                for carry in [false, true] {
+                    let expected = ((a & 0xF) + (b & 0xF) + carry as u8) > 0xF;
+                    assert_eq!(half_carry_add(a, b, carry), expected, "a={:02X} b={:02X} c={}", a, b, carry);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_half_carry_sub_exhaustive() {
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                for borrow in [false, true] {
+                    // 下位4bitの減算結果が負になれば桁借り
+                    let expected = ((a & 0xF) as i16 - (b & 0xF) as i16 - borrow as i16) < 0;
+                    assert_eq!(half_carry_sub(a, b, borrow), expected, "a={:02X} b={:02X} c={}", a, b, borrow);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_half_carry_add16() {
+        assert!(half_carry_add16(0x0FFF, 0x0001));
+        assert!(!half_carry_add16(0x0FFE, 0x0001));
+        assert!(!half_carry_add16(0xF000, 0xF000));
+        assert!(half_carry_add16(0x0800, 0x0800));
+    }
+}
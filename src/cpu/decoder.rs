@@ -1,7 +1,61 @@
 // src/cpu/decoder.rs
 // GameBoy CPU 命令デコーダ
 
-use super::instructions::{InstructionTable, Instruction, InstructionType};
+use alloc::{format, string::String, vec::Vec};
+use super::instructions::{InstructionTable, Instruction, InstructionType, BASE_CYCLES, CB_CYCLES};
+
+/// 未定義オペコード（実機では実行するとCPUがロックする。実装対象外）
+pub const ILLEGAL_OPCODES: [u8; 11] = [
+    0xD3, 0xDB, 0xDD, 0xE3, 0xE4, 0xEB, 0xEC, 0xED, 0xF4, 0xFC, 0xFD,
+];
+
+/// タイミングテーブルには載っているがCPUが未実装のオペコード（STOP）
+pub const UNIMPLEMENTED_OPCODES: [u8; 1] = [0x10];
+
+/// オペコード実装状況のレポート
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// 実装済みの基本オペコード
+    pub implemented: Vec<u8>,
+    /// 未実装の基本オペコード（未定義オペコードを除く）
+    pub missing: Vec<u8>,
+    /// 未定義オペコード
+    pub illegal: Vec<u8>,
+    /// 実装済みのCBオペコード
+    pub cb_implemented: Vec<u8>,
+    /// 未実装のCBオペコード
+    pub cb_missing: Vec<u8>,
+}
+
+impl CoverageReport {
+    /// 実装済みオペコードの合計（基本 + CB、最大 245 + 256 = 501）
+    pub fn implemented_count(&self) -> usize {
+        self.implemented.len() + self.cb_implemented.len()
+    }
+
+    /// 未実装オペコードの合計（未定義オペコードを除く）
+    pub fn missing_count(&self) -> usize {
+        self.missing.len() + self.cb_missing.len()
+    }
+
+    /// レポートを文字列で取得
+    pub fn summary(&self) -> String {
+        let mut result = format!(
+            "オペコード実装状況:\n  基本: {}/{} (未定義: {})\n  CB: {}/256\n",
+            self.implemented.len(),
+            256 - self.illegal.len(),
+            self.illegal.len(),
+            self.cb_implemented.len()
+        );
+        for opcode in &self.missing {
+            result.push_str(&format!("  未実装: 0x{:02X}\n", opcode));
+        }
+        for opcode in &self.cb_missing {
+            result.push_str(&format!("  未実装: 0xCB{:02X}\n", opcode));
+        }
+        result
+    }
+}
 
 /// 命令デコーダ
 pub struct InstructionDecoder {
@@ -66,6 +120,37 @@ impl InstructionDecoder {
         result
     }
     
+    /// 命令のタイミングテーブルから、基本256 + CB256オペコードのカバレッジを取得
+    ///
+    /// サイクル数0のオペコードを未定義、UNIMPLEMENTED_OPCODESを未実装として扱う
+    pub fn coverage() -> CoverageReport {
+        let mut report = CoverageReport {
+            implemented: Vec::new(),
+            missing: Vec::new(),
+            illegal: Vec::new(),
+            cb_implemented: Vec::new(),
+            cb_missing: Vec::new(),
+        };
+
+        for opcode in 0..=0xFFu8 {
+            if BASE_CYCLES[opcode as usize] == 0 {
+                report.illegal.push(opcode);
+            } else if UNIMPLEMENTED_OPCODES.contains(&opcode) {
+                report.missing.push(opcode);
+            } else {
+                report.implemented.push(opcode);
+            }
+
+            if CB_CYCLES[opcode as usize] == 0 {
+                report.cb_missing.push(opcode);
+            } else {
+                report.cb_implemented.push(opcode);
+            }
+        }
+
+        report
+    }
+    
     /// 命令タイプ別の統計を取得
     pub fn get_instruction_stats(&self) -> String {
         let opcodes = self.instruction_table.get_implemented_opcodes();
@@ -173,4 +258,17 @@ mod tests {
         assert!(stats.contains("LOAD:"));
         assert!(stats.contains("JUMP:"));
     }
+    
+    #[test]
+    fn test_opcode_coverage() {
+        let report = InstructionDecoder::coverage();
+        
+        // 未知のオペコードは未定義の11個だけで、未実装ではなく未定義として分類される
+        assert_eq!(report.illegal, ILLEGAL_OPCODES.to_vec());
+        assert_eq!(report.missing, UNIMPLEMENTED_OPCODES.to_vec());
+        assert!(report.cb_missing.is_empty());
+        assert_eq!(report.implemented.len() + report.missing.len() + report.illegal.len(), 256);
+        assert_eq!(report.missing_count(), UNIMPLEMENTED_OPCODES.len());
+        assert!(report.summary().contains("未実装: 0x10"));
+    }
 }
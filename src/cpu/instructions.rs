@@ -1,6 +1,8 @@
 // src/cpu/instructions.rs
 // GameBoy CPU 命令定義
 
+use alloc::{format, string::String, vec::Vec};
+
 /// 命令の種類
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InstructionType {
@@ -24,12 +26,84 @@ pub enum Register8 {
     A, B, C, D, E, H, L,
 }
 
+impl Register8 {
+    /// オペコード中の3bitレジスタ番号から変換（6は(HL)のためNone）
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code & 0x07 {
+            0 => Some(Self::B),
+            1 => Some(Self::C),
+            2 => Some(Self::D),
+            3 => Some(Self::E),
+            4 => Some(Self::H),
+            5 => Some(Self::L),
+            7 => Some(Self::A),
+            _ => None,
+        }
+    }
+}
+
 /// 16bitレジスタの識別子
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Register16 {
     AF, BC, DE, HL, SP, PC,
 }
 
+/// オペランドの種類（逆アセンブル時の表示形式を決める）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperandKind {
+    /// オペランドなし
+    None,
+    /// 8bit即値 (n)
+    Immediate8,
+    /// 符号付き8bit即値 (ADD SP, e / LD HL, SP+e)
+    Signed8,
+    /// 符号付き8bit相対アドレス (JR)
+    Relative8,
+    /// 16bit即値/アドレス (nn)
+    Immediate16,
+    /// 0xFF00からのオフセット (LDH)
+    HighOffset8,
+}
+
+impl OperandKind {
+    /// 命令長から既定のオペランド種類を決める
+    pub fn from_length(length: u8) -> Self {
+        match length {
+            2 => Self::Immediate8,
+            3 => Self::Immediate16,
+            _ => Self::None,
+        }
+    }
+
+    /// オペランドのバイト数
+    pub fn size(&self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Immediate16 => 2,
+            _ => 1,
+        }
+    }
+
+    /// オペランドバイト列（リトルエンディアン）を表示用文字列に変換
+    ///
+    /// 例: Immediate16 → `$C004`、Relative8 → `$-3`、HighOffset8 → `$FF44`
+    pub fn format(&self, bytes: &[u8]) -> String {
+        let byte = |i: usize| bytes.get(i).copied().unwrap_or(0);
+        match self {
+            Self::None => String::new(),
+            Self::Immediate8 => format!("${:02X}", byte(0)),
+            Self::Signed8 => {
+                let value = byte(0) as i8;
+                let sign = if value < 0 { '-' } else { '+' };
+                format!("{}${:02X}", sign, value.unsigned_abs())
+            }
+            Self::Relative8 => format!("${:+}", byte(0) as i8),
+            Self::Immediate16 => format!("${:04X}", u16::from_le_bytes([byte(0), byte(1)])),
+            Self::HighOffset8 => format!("$FF{:02X}", byte(0)),
+        }
+    }
+}
+
 /// 命令の情報
 #[derive(Debug, Clone, Copy)]
 pub struct Instruction {
@@ -45,6 +119,8 @@ pub struct Instruction {
     pub reg8: Option<Register8>,
     /// 対象レジスタ（16bit）
     pub reg16: Option<Register16>,
+    /// オペランドの種類
+    pub operand: OperandKind,
     /// 命令の説明
     pub description: &'static str,
 }
@@ -65,6 +141,7 @@ impl Instruction {
             cycles,
             reg8: None,
             reg16: None,
+            operand: OperandKind::from_length(length),
             description,
         }
     }
@@ -80,6 +157,69 @@ impl Instruction {
         self.reg16 = Some(reg);
         self
     }
+
+    /// オペランドの種類を指定した命令を作成（命令長からの推定を上書き）
+    pub fn with_operand(mut self, operand: OperandKind) -> Self {
+        self.operand = operand;
+        self
+    }
+}
+
+/// 基本命令の実行サイクル数（Tサイクル、条件分岐は不成立時、0は未定義オペコード）
+///
+/// 0xCBはプレフィックスのみの値。CB命令はプレフィックスを含めた値をCB_CYCLESに持つ
+pub const BASE_CYCLES: [u8; 256] = [
+//  x0  x1  x2  x3  x4  x5  x6  x7  x8  x9  xA  xB  xC  xD  xE  xF
+     4, 12,  8,  8,  4,  4,  8,  4, 20,  8,  8,  8,  4,  4,  8,  4, // 0x
+     4, 12,  8,  8,  4,  4,  8,  4, 12,  8,  8,  8,  4,  4,  8,  4, // 1x
+     8, 12,  8,  8,  4,  4,  8,  4,  8,  8,  8,  8,  4,  4,  8,  4, // 2x
+     8, 12,  8,  8, 12, 12, 12,  4,  8,  8,  8,  8,  4,  4,  8,  4, // 3x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // 4x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // 5x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // 6x
+     8,  8,  8,  8,  8,  8,  4,  8,  4,  4,  4,  4,  4,  4,  8,  4, // 7x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // 8x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // 9x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // Ax
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // Bx
+     8, 12, 12, 16, 12, 16,  8, 16,  8, 16, 12,  4, 12, 24,  8, 16, // Cx
+     8, 12, 12,  0, 12, 16,  8, 16,  8, 16, 12,  0, 12,  0,  8, 16, // Dx
+    12, 12,  8,  0,  0, 16,  8, 16, 16,  4, 16,  0,  0,  0,  8, 16, // Ex
+    12, 12,  8,  4,  0, 16,  8, 16, 12,  8, 16,  4,  0,  0,  8, 16, // Fx
+];
+
+/// CB命令の実行サイクル数（Tサイクル、プレフィックスを含む）
+///
+/// レジスタ対象は8、(HL)対象は16、BIT b,(HL)のみ書き戻しがないため12
+pub const CB_CYCLES: [u8; 256] = {
+    let mut table = [8u8; 256];
+    let mut opcode = 0;
+    while opcode < 256 {
+        if opcode & 0x07 == 6 {
+            table[opcode] = if opcode >= 0x40 && opcode < 0x80 { 12 } else { 16 };
+        }
+        opcode += 1;
+    }
+    table
+};
+
+/// 条件分岐命令の条件成立時のサイクル数（条件分岐でなければNone）
+pub fn conditional_taken_cycles(opcode: u8) -> Option<u8> {
+    match opcode {
+        0x20 | 0x28 | 0x30 | 0x38 => Some(12), // JR cc, n
+        0xC0 | 0xC8 | 0xD0 | 0xD8 => Some(20), // RET cc
+        0xC2 | 0xCA | 0xD2 | 0xDA => Some(16), // JP cc, nn
+        0xC4 | 0xCC | 0xD4 | 0xDC => Some(24), // CALL cc, nn
+        _ => None,
+    }
+}
+
+/// 基本命令のサイクル数（taken: 条件分岐が成立したか、条件分岐以外では無視）
+pub fn base_cycles(opcode: u8, taken: bool) -> u8 {
+    match conditional_taken_cycles(opcode) {
+        Some(cycles) if taken => cycles,
+        _ => BASE_CYCLES[opcode as usize],
+    }
 }
 
 /// 命令テーブル
@@ -154,10 +294,12 @@ impl InstructionTable {
         self.add_instruction(
             0xC3,
             Instruction::new(InstructionType::JpNN, 0xC3, 3, 16, "JP nn")
+                .with_operand(OperandKind::Immediate16)
         );
         self.add_instruction(
             0x18,
             Instruction::new(InstructionType::JrN, 0x18, 2, 12, "JR n")
+                .with_operand(OperandKind::Relative8)
         );
     }
     
@@ -238,4 +380,47 @@ mod tests {
         // 最低限の命令数が実装されていることを確認
         assert!(opcodes.len() >= 10);
     }
+    
+    #[test]
+    fn test_operand_kinds() {
+        let table = InstructionTable::new();
+
+        assert_eq!(table.get_instruction(0x18).unwrap().operand, OperandKind::Relative8); // JR n
+        assert_eq!(table.get_instruction(0x3E).unwrap().operand, OperandKind::Immediate8); // LD A, n
+        assert_eq!(table.get_instruction(0xC3).unwrap().operand, OperandKind::Immediate16); // JP nn
+        assert_eq!(table.get_instruction(0x00).unwrap().operand, OperandKind::None); // NOP
+
+        // オペランドのバイト数は命令長と一致する
+        for opcode in table.get_implemented_opcodes() {
+            let instruction = table.get_instruction(opcode).unwrap();
+            assert_eq!(instruction.operand.size() + 1, instruction.length, "opcode {:02X}", opcode);
+        }
+    }
+
+    #[test]
+    fn test_operand_format() {
+        assert_eq!(OperandKind::Immediate16.format(&[0x04, 0xC0]), "$C004");
+        assert_eq!(OperandKind::Immediate8.format(&[0x7F]), "$7F");
+        assert_eq!(OperandKind::Relative8.format(&[0xFD]), "$-3");
+        assert_eq!(OperandKind::Relative8.format(&[0x05]), "$+5");
+        assert_eq!(OperandKind::Signed8.format(&[0xF8]), "-$08");
+        assert_eq!(OperandKind::Signed8.format(&[0x10]), "+$10");
+        assert_eq!(OperandKind::HighOffset8.format(&[0x44]), "$FF44");
+        assert_eq!(OperandKind::None.format(&[]), "");
+    }
+
+    #[test]
+    fn test_instruction_table_matches_cycle_table() {
+        let table = InstructionTable::new();
+        for opcode in table.get_implemented_opcodes() {
+            let instruction = table.get_instruction(opcode).unwrap();
+            assert_eq!(instruction.cycles, BASE_CYCLES[opcode as usize], "opcode {:02X}", opcode);
+        }
+        assert_eq!(base_cycles(0x20, false), 8);
+        assert_eq!(base_cycles(0x20, true), 12);
+        assert_eq!(base_cycles(0x00, true), 4);
+        assert_eq!(CB_CYCLES[0x46], 12); // BIT 0, (HL)
+        assert_eq!(CB_CYCLES[0x86], 16); // RES 0, (HL)
+        assert_eq!(CB_CYCLES[0x37], 8);  // SWAP A
+    }
 }
@@ -48,13 +48,10 @@ pub fn get_pending_interrupt(interrupt_flag: u8, interrupt_enable: u8) -> Option
         return None;
     }
 
-    for &interrupt in Interrupt::all_by_priority() {
-        if pending & interrupt.mask() != 0 {
-            return Some(interrupt);
-        }
-    }
-
-    None
+    Interrupt::all_by_priority()
+        .iter()
+        .find(|&&interrupt| pending & interrupt.mask() != 0)
+        .copied()
 }
 
 /// 保留中の割り込みがあるかどうかだけを確認（HALTからの復帰判定用）
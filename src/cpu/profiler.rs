@@ -0,0 +1,127 @@
+// src/cpu/profiler.rs
+// オペコード実行回数のプロファイラ
+//
+// Cpu::set_opcode_hookに登録するフックでフェッチしたオペコードを集計し、
+// 実行回数の多い命令（最適化対象）をヒストグラムとして取り出す。
+// CB-prefix命令は0xCBとして1回に数える（2バイト目は区別しない）。
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use super::OpcodeHook;
+
+/// オペコードごとの実行回数
+pub struct OpcodeProfiler {
+    counts: [u64; 256],
+}
+
+impl OpcodeProfiler {
+    pub fn new() -> Self {
+        Self { counts: [0; 256] }
+    }
+
+    /// 共有されたプロファイラに集計するフックを作成
+    pub fn hook(profiler: &Rc<RefCell<Self>>) -> OpcodeHook {
+        let profiler = Rc::clone(profiler);
+        Box::new(move |opcode, _pc| profiler.borrow_mut().record(opcode))
+    }
+
+    /// オペコードの実行を1回記録
+    pub fn record(&mut self, opcode: u8) {
+        self.counts[opcode as usize] += 1;
+    }
+
+    /// 指定オペコードの実行回数
+    pub fn count(&self, opcode: u8) -> u64 {
+        self.counts[opcode as usize]
+    }
+
+    /// 記録した命令の総数
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// 実行されたオペコードと回数の一覧（回数の多い順、同数はオペコード順）
+    pub fn histogram(&self) -> Vec<(u8, u64)> {
+        let mut histogram: Vec<(u8, u64)> = self.counts.iter()
+            .enumerate()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(opcode, &count)| (opcode as u8, count))
+            .collect();
+        histogram.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        histogram
+    }
+
+    /// 集計をクリア
+    pub fn clear(&mut self) {
+        self.counts = [0; 256];
+    }
+}
+
+impl Default for OpcodeProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::Cpu;
+    use crate::memory::BootRom;
+    use crate::peripherals::Peripherals;
+
+    #[test]
+    fn test_opcode_profiler_counts_instruction_mix() {
+        let mut cpu = Cpu::new();
+        let mut peripherals = Peripherals::new(BootRom::new_dummy());
+        peripherals.write(0xFF50, 0x01);
+
+        // loop: NOP / NOP / LD A,0x01 / JR loop
+        let program = [0x00, 0x00, 0x3E, 0x01, 0x18, 0xFA];
+        for (i, &byte) in program.iter().enumerate() {
+            peripherals.write(0xC000 + i as u16, byte);
+        }
+        cpu.registers.pc = 0xC000;
+
+        let profiler = Rc::new(RefCell::new(OpcodeProfiler::new()));
+        cpu.set_opcode_hook(OpcodeProfiler::hook(&profiler));
+
+        // 1周4命令 × 10周
+        for _ in 0..40 {
+            cpu.step(&mut peripherals).unwrap();
+        }
+
+        let profiler = profiler.borrow();
+        assert_eq!(profiler.total(), 40);
+        assert_eq!(profiler.count(0x00), 20);
+        assert_eq!(profiler.count(0x3E), 10);
+        assert_eq!(profiler.count(0x18), 10);
+        assert_eq!(profiler.histogram(), [(0x00, 20), (0x18, 10), (0x3E, 10)]);
+    }
+
+    #[test]
+    fn test_opcode_hook_receives_pc() {
+        let mut cpu = Cpu::new();
+        let mut peripherals = Peripherals::new(BootRom::new_dummy());
+        peripherals.write(0xFF50, 0x01);
+        peripherals.write(0xC000, 0x00); // NOP
+        peripherals.write(0xC001, 0x3E); // LD A,0x55
+        peripherals.write(0xC002, 0x55);
+        cpu.registers.pc = 0xC000;
+
+        let seen: Rc<RefCell<Vec<(u8, u16)>>> = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&seen);
+        cpu.set_opcode_hook(Box::new(move |opcode, pc| sink.borrow_mut().push((opcode, pc))));
+        cpu.step(&mut peripherals).unwrap();
+        cpu.step(&mut peripherals).unwrap();
+        assert_eq!(*seen.borrow(), [(0x00, 0xC000), (0x3E, 0xC001)]);
+
+        // 解除後は呼ばれない
+        assert!(cpu.clear_opcode_hook().is_some());
+        cpu.registers.pc = 0xC000;
+        cpu.step(&mut peripherals).unwrap();
+        assert_eq!(seen.borrow().len(), 2);
+    }
+}
@@ -1,6 +1,9 @@
 // src/cpu/registers.rs
 // GameBoy CPU レジスタシステム
 
+use alloc::{format, string::String};
+use super::instructions::{Register8, Register16};
+
 /// GameBoy CPU のフラグレジスタビット定義
 pub mod flags {
     pub const ZERO: u8 = 0b1000_0000;        // Z: Zero flag
@@ -15,8 +18,8 @@ pub mod flags {
 pub struct Registers {
     /// アキュムレータ
     pub a: u8,
-    /// フラグレジスタ（下位4bitは常に0）
-    pub f: u8,
+    /// フラグレジスタ（下位4bitは常に0、クレート外からはf/set_fでアクセス）
+    pub(crate) f: u8,
     /// 汎用レジスタB
     pub b: u8,
     /// 汎用レジスタC
@@ -52,16 +55,42 @@ impl Registers {
         }
     }
     
+    /// BootROM実行後のレジスタ値で作成（DMG、PC=0x0100から開始）
+    pub fn new_post_boot() -> Self {
+        Self {
+            a: 0x01,
+            f: 0xB0,
+            b: 0x00,
+            c: 0x13,
+            d: 0x00,
+            e: 0xD8,
+            h: 0x01,
+            l: 0x4D,
+            sp: 0xFFFE,
+            pc: 0x0100,
+        }
+    }
+    
     /// レジスタを初期状態にリセット
     pub fn reset(&mut self) {
         *self = Self::new();
     }
     
-    /// 16bitレジスタペアのアクセサ
+    /// フラグレジスタを取得
+    pub fn f(&self) -> u8 {
+        self.f & 0xF0
+    }
+
+    /// フラグレジスタを設定（下位4bitは常に0）
+    pub fn set_f(&mut self, value: u8) {
+        self.f = value & 0xF0;
+    }
+
+    // 16bitレジスタペアのアクセサ
     
-    /// AF レジスタペアを取得
+    /// AF レジスタペアを取得（Fの下位4bitは常に0として返す）
     pub fn af(&self) -> u16 {
-        ((self.a as u16) << 8) | (self.f as u16)
+        ((self.a as u16) << 8) | (self.f() as u16)
     }
     
     /// AF レジスタペアを設定
@@ -103,6 +132,58 @@ impl Registers {
         self.l = value as u8;
     }
     
+    // 識別子によるレジスタアクセス（汎用的な命令実装から使用）
+
+    /// 8bitレジスタを識別子で取得
+    pub fn get_r8(&self, reg: Register8) -> u8 {
+        match reg {
+            Register8::A => self.a,
+            Register8::B => self.b,
+            Register8::C => self.c,
+            Register8::D => self.d,
+            Register8::E => self.e,
+            Register8::H => self.h,
+            Register8::L => self.l,
+        }
+    }
+
+    /// 8bitレジスタを識別子で設定
+    pub fn set_r8(&mut self, reg: Register8, value: u8) {
+        match reg {
+            Register8::A => self.a = value,
+            Register8::B => self.b = value,
+            Register8::C => self.c = value,
+            Register8::D => self.d = value,
+            Register8::E => self.e = value,
+            Register8::H => self.h = value,
+            Register8::L => self.l = value,
+        }
+    }
+
+    /// 16bitレジスタを識別子で取得
+    pub fn get_r16(&self, reg: Register16) -> u16 {
+        match reg {
+            Register16::AF => self.af(),
+            Register16::BC => self.bc(),
+            Register16::DE => self.de(),
+            Register16::HL => self.hl(),
+            Register16::SP => self.sp,
+            Register16::PC => self.pc,
+        }
+    }
+
+    /// 16bitレジスタを識別子で設定（AFはFの下位4bitをマスク）
+    pub fn set_r16(&mut self, reg: Register16, value: u16) {
+        match reg {
+            Register16::AF => self.set_af(value),
+            Register16::BC => self.set_bc(value),
+            Register16::DE => self.set_de(value),
+            Register16::HL => self.set_hl(value),
+            Register16::SP => self.sp = value,
+            Register16::PC => self.pc = value,
+        }
+    }
+
     // get_xxx エイリアス（CPU命令実装から使用）
     pub fn get_af(&self) -> u16 { self.af() }
     pub fn get_bc(&self) -> u16 { self.bc() }
@@ -113,7 +194,7 @@ impl Registers {
     pub fn get_flag_z(&self) -> bool { self.zero_flag() }
     pub fn get_flag_c(&self) -> bool { self.carry_flag() }
 
-    /// フラグ操作
+    // フラグ操作
 
     /// Zero flag を取得
     pub fn zero_flag(&self) -> bool {
@@ -267,11 +348,52 @@ mod tests {
         let mut regs = Registers::new();
         
         // Fレジスタの下位4bitは常に0になることを確認
-        regs.f = 0xFF;
-        assert_eq!(regs.f, 0xFF);
+        regs.set_f(0xFF);
+        assert_eq!(regs.f(), 0xF0);
         
         regs.set_af(0x12FF);
         assert_eq!(regs.f, 0xF0); // 下位4bitがマスクされる
+
+        // 内部で下位4bitが立っていてもafには現れない
+        regs.f = 0x0F;
+        assert_eq!(regs.af(), 0x1200);
+    }
+
+    #[test]
+    fn test_register_pair_round_trip_fuzz() {
+        // 線形合同法で擬似乱数の16bit値を生成
+        let mut state = 0x1234_5678u32;
+        let mut next = move || {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            (state >> 8) as u16
+        };
+
+        let mut regs = Registers::new();
+        for _ in 0..10_000 {
+            let (af, bc, de, hl) = (next(), next(), next(), next());
+            regs.set_af(af);
+            regs.set_bc(bc);
+            regs.set_de(de);
+            regs.set_hl(hl);
+
+            // 各ペアは互いに干渉せず、上位バイトが先頭レジスタ（A/B/D/H）に入る
+            assert_eq!(regs.af(), af & 0xFFF0);
+            assert_eq!(regs.bc(), bc);
+            assert_eq!(regs.de(), de);
+            assert_eq!(regs.hl(), hl);
+            assert_eq!((regs.a, regs.b, regs.d, regs.h), ((af >> 8) as u8, (bc >> 8) as u8, (de >> 8) as u8, (hl >> 8) as u8));
+            assert_eq!((regs.c, regs.e, regs.l), (bc as u8, de as u8, hl as u8));
+            assert_eq!(regs.f & 0x0F, 0);
+
+            // 識別子経由でも同じ
+            regs.set_r16(Register16::AF, bc);
+            assert_eq!(regs.get_r16(Register16::AF), bc & 0xFFF0);
+            assert_eq!(regs.f & 0x0F, 0);
+
+            regs.set_f(de as u8);
+            assert_eq!(regs.f & 0x0F, 0);
+            assert_eq!(regs.af() & 0x000F, 0);
+        }
     }
     
     #[test]
@@ -286,4 +408,25 @@ mod tests {
         regs.set_flags(true, true, true, true);
         assert_eq!(regs.flags_string(), "ZNHC");
     }
+    
+    #[test]
+    fn test_indexed_register_access() {
+        let mut regs = Registers::new();
+        
+        regs.set_r8(Register8::H, 0x9A);
+        assert_eq!(regs.h, 0x9A);
+        assert_eq!(regs.get_r8(Register8::H), 0x9A);
+        
+        regs.set_r16(Register16::AF, 0x12FF);
+        assert_eq!(regs.a, 0x12);
+        assert_eq!(regs.f, 0xF0); // 下位4bitはマスクされる
+        assert_eq!(regs.get_r16(Register16::AF), 0x12F0);
+        
+        regs.set_r16(Register16::HL, 0xBEEF);
+        assert_eq!(regs.get_r8(Register8::H), 0xBE);
+        assert_eq!(regs.get_r8(Register8::L), 0xEF);
+        
+        regs.set_r16(Register16::SP, 0xFFFE);
+        assert_eq!(regs.get_r16(Register16::SP), 0xFFFE);
+    }
 }
@@ -1,11 +1,15 @@
 // src/cpu/timer.rs
 // GameBoy タイマーシステム
 
+/// BootROM終了直後の内部カウンタ値 (DMG: DIV=0xAB)
+pub const POST_BOOT_INTERNAL_COUNTER: u16 = 0xABCC;
+
 /// GameBoy タイマー
 /// DIV: 16bit内部カウンタ（上位8bitを0xFF04で読み出し）
 /// TIMA: タイマーカウンタ（0xFF05、オーバーフローで割り込み）
 /// TMA: タイマーモジュロ（0xFF06、TIMAオーバーフロー時のリロード値）
 /// TAC: タイマー制御（0xFF07、有効/無効・周波数選択）
+#[derive(Clone)]
 pub struct Timer {
     /// 内部16bitカウンタ（DIVは上位8bit）
     pub internal_counter: u16,
@@ -16,7 +20,7 @@ pub struct Timer {
     /// タイマー制御
     pub tac: u8,
     /// Timer割り込み要求フラグ
-    pub interrupt_request: bool,
+    interrupt_request: bool,
 }
 
 impl Timer {
@@ -30,8 +34,29 @@ impl Timer {
         }
     }
 
-    /// 1 Tサイクル分タイマーを進める
-    pub fn tick(&mut self) {
+    /// BootROM実行後の状態でタイマーを作成（BootROMをスキップする場合に使用）
+    pub fn new_post_boot() -> Self {
+        Self {
+            internal_counter: POST_BOOT_INTERNAL_COUNTER,
+            ..Self::new()
+        }
+    }
+
+    /// 指定Tサイクル分タイマーを進める（内部カウンタはTACに関係なく常に進む）
+    ///
+    /// タイマー無効時はTIMAが変化しないため、内部カウンタ（DIV）にまとめて加算する
+    pub fn tick(&mut self, cycles: u32) {
+        if !self.is_enabled() {
+            self.internal_counter = self.internal_counter.wrapping_add(cycles as u16);
+            return;
+        }
+        for _ in 0..cycles {
+            self.tick_cycle();
+        }
+    }
+
+    // 1 Tサイクル分タイマーを進める
+    fn tick_cycle(&mut self) {
         let old_counter = self.internal_counter;
         self.internal_counter = self.internal_counter.wrapping_add(1);
 
@@ -55,6 +80,25 @@ impl Timer {
         }
     }
 
+    /// TIMAがオーバーフローして割り込みが発生するまでのサイクル数（タイマー無効時はNone）
+    ///
+    /// TACとTIMAが書き換えられなければ、このサイクル数だけtickした最後のサイクルで割り込みが要求される
+    pub fn cycles_until_interrupt(&self) -> Option<u32> {
+        if !self.is_enabled() {
+            return None;
+        }
+        // 監視ビットの立ち下がりは内部カウンタが周期の倍数になるサイクル
+        let period = 1u32 << (self.get_clock_bit() + 1);
+        let first_edge = period - (self.internal_counter as u32 % period);
+        let increments = 0x100 - self.tima as u32;
+        Some(first_edge + (increments - 1) * period)
+    }
+
+    /// 割り込み要求を取得してクリア（要求があればtrueを一度だけ返す）
+    pub fn take_interrupt(&mut self) -> bool {
+        core::mem::take(&mut self.interrupt_request)
+    }
+
     /// DIVレジスタ読み出し（内部カウンタの上位8bit）
     pub fn read_div(&self) -> u8 {
         (self.internal_counter >> 8) as u8
@@ -87,6 +131,12 @@ impl Timer {
     }
 }
 
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,7 +155,7 @@ mod tests {
         let mut timer = Timer::new();
         // 256 Tサイクルで DIV が1増える
         for _ in 0..256 {
-            timer.tick();
+            timer.tick(1);
         }
         assert_eq!(timer.read_div(), 1);
     }
@@ -114,7 +164,7 @@ mod tests {
     fn test_div_write_resets() {
         let mut timer = Timer::new();
         for _ in 0..512 {
-            timer.tick();
+            timer.tick(1);
         }
         assert_eq!(timer.read_div(), 2);
         timer.write_div();
@@ -127,7 +177,7 @@ mod tests {
         timer.tac = 0x00; // 無効
         timer.tima = 0;
         for _ in 0..10000 {
-            timer.tick();
+            timer.tick(1);
         }
         assert_eq!(timer.tima, 0); // TIMAは変化しない
     }
@@ -142,7 +192,7 @@ mod tests {
         // CPU/16 = 16 Tサイクルで1回TIMAインクリメント
         // bit 3のfalling edgeは内部カウンタが8→0の遷移（16サイクル毎）
         for _ in 0..16 {
-            timer.tick();
+            timer.tick(1);
         }
 
         // TIMAがオーバーフロー → TMAリロード + 割り込み
@@ -150,6 +200,28 @@ mod tests {
         assert!(timer.interrupt_request);
     }
 
+    #[test]
+    fn test_timer_take_interrupt() {
+        let mut timer = Timer::new();
+        timer.tac = 0x05; // 有効、CPU/16
+        timer.tima = 0xFF;
+
+        assert!(!timer.take_interrupt());
+        for _ in 0..16 {
+            timer.tick(1);
+        }
+
+        // 一度だけtrueを返し、次の要求まではfalse
+        assert!(timer.take_interrupt());
+        assert!(!timer.take_interrupt());
+
+        timer.tima = 0xFF;
+        for _ in 0..16 {
+            timer.tick(1);
+        }
+        assert!(timer.take_interrupt());
+    }
+
     #[test]
     fn test_timer_frequency_selection() {
         // CPU/16モード: 16 Tサイクルで1回TIMAインクリメント
@@ -158,13 +230,72 @@ mod tests {
         timer.tima = 0x00;
 
         for _ in 0..16 {
-            timer.tick();
+            timer.tick(1);
         }
         assert_eq!(timer.tima, 1);
 
         for _ in 0..16 {
-            timer.tick();
+            timer.tick(1);
         }
         assert_eq!(timer.tima, 2);
     }
+
+    #[test]
+    fn test_timer_post_boot_div() {
+        let mut timer = Timer::new_post_boot();
+        assert_eq!(timer.read_div(), 0xAB);
+        assert!(!timer.is_enabled());
+
+        // タイマー無効でもDIVは256サイクルごとに進む (0xABCC → 0xAC00 まで52サイクル)
+        for _ in 0..52 {
+            timer.tick(1);
+        }
+        assert_eq!(timer.read_div(), 0xAC);
+        for _ in 0..256 {
+            timer.tick(1);
+        }
+        assert_eq!(timer.read_div(), 0xAD);
+        assert_eq!(timer.tima, 0);
+    }
+
+    #[test]
+    fn test_timer_bulk_tick_matches_single_ticks() {
+        // (TAC, TMA, 初期TIMA): 無効、各周波数でTIMAのオーバーフローとリロードを含む
+        for (tac, tma, tima) in [(0x00, 0x00, 0x00), (0x05, 0xF0, 0xFE), (0x04, 0x80, 0xFF), (0x07, 0x00, 0x10)] {
+            let mut bulk = Timer::new();
+            let mut single = Timer::new();
+            for timer in [&mut bulk, &mut single] {
+                timer.tac = tac;
+                timer.tma = tma;
+                timer.tima = tima;
+            }
+
+            for cycles in [1u32, 4, 12, 20, 1000, 70224] {
+                bulk.tick(cycles);
+                for _ in 0..cycles {
+                    single.tick(1);
+                }
+                assert_eq!(bulk.internal_counter, single.internal_counter, "TAC={:02X}", tac);
+                assert_eq!(bulk.tima, single.tima, "TAC={:02X}", tac);
+                assert_eq!(bulk.take_interrupt(), single.take_interrupt(), "TAC={:02X}", tac);
+            }
+        }
+    }
+
+    #[test]
+    fn test_timer_cycles_until_interrupt() {
+        let mut timer = Timer::new();
+        assert_eq!(timer.cycles_until_interrupt(), None);
+
+        for (tac, tima, counter) in [(0x05, 0xFF, 0x0000), (0x05, 0xF0, 0x0007), (0x04, 0x80, 0x03FF), (0x07, 0x00, 0xFFFF)] {
+            timer.tac = tac;
+            timer.tima = tima;
+            timer.internal_counter = counter;
+            let cycles = timer.cycles_until_interrupt().unwrap();
+            timer.tick(cycles - 1);
+            assert!(!timer.take_interrupt(), "TAC={:02X} TIMA={:02X}", tac, tima);
+            timer.tick(1);
+            assert!(timer.take_interrupt(), "TAC={:02X} TIMA={:02X}", tac, tima);
+        }
+    }
 }
@@ -0,0 +1,73 @@
+// src/display_rect.rs
+// ウィンドウ内での画面描画先矩形の計算
+//
+// SDL2等の表示バックエンドに依存しない純粋な幾何計算のみを置く。
+// lcd.rs（with_sdl機能）はウィンドウのリサイズ時にここを呼び出す。
+
+const SCREEN_WIDTH: u32 = 160;
+const SCREEN_HEIGHT: u32 = 144;
+
+// 画面の描画先矩形（ウィンドウ座標）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+// ウィンドウサイズから画面の描画先矩形を計算
+// integer_scale: 整数倍に切り捨て（ドットの大きさを均一に保つ）
+// keep_aspect: 160:144の比率を保ち、余白はレターボックスにする
+pub fn compute_display_rect(window_width: u32, window_height: u32, integer_scale: bool, keep_aspect: bool) -> DisplayRect {
+    let (width, height) = if integer_scale {
+        let scale = (window_width / SCREEN_WIDTH).min(window_height / SCREEN_HEIGHT).max(1);
+        (SCREEN_WIDTH * scale, SCREEN_HEIGHT * scale)
+    } else if keep_aspect {
+        // 幅基準と高さ基準のうち小さい方に合わせる
+        if window_width * SCREEN_HEIGHT <= window_height * SCREEN_WIDTH {
+            (window_width, window_width * SCREEN_HEIGHT / SCREEN_WIDTH)
+        } else {
+            (window_height * SCREEN_WIDTH / SCREEN_HEIGHT, window_height)
+        }
+    } else {
+        (window_width, window_height)
+    };
+    
+    // 中央寄せ（ウィンドウより大きい場合は左上に揃える）
+    DisplayRect {
+        x: (window_width.saturating_sub(width) / 2) as i32,
+        y: (window_height.saturating_sub(height) / 2) as i32,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_display_rect_integer_scale() {
+        // ちょうど4倍
+        assert_eq!(compute_display_rect(640, 576, true, true), DisplayRect { x: 0, y: 0, width: 640, height: 576 });
+        
+        // 700x600 → 4倍に切り捨てて中央寄せ
+        assert_eq!(compute_display_rect(700, 600, true, true), DisplayRect { x: 30, y: 12, width: 640, height: 576 });
+        
+        // ウィンドウが1倍より小さくても最低1倍
+        assert_eq!(compute_display_rect(100, 100, true, true), DisplayRect { x: 0, y: 0, width: 160, height: 144 });
+    }
+    
+    #[test]
+    fn test_display_rect_keep_aspect() {
+        // 横長ウィンドウ → 左右に余白
+        assert_eq!(compute_display_rect(1000, 576, false, true), DisplayRect { x: 180, y: 0, width: 640, height: 576 });
+        
+        // 縦長ウィンドウ → 上下に余白
+        assert_eq!(compute_display_rect(320, 600, false, true), DisplayRect { x: 0, y: 156, width: 320, height: 288 });
+        
+        // アスペクト比維持なし → ウィンドウ全体に引き伸ばし
+        assert_eq!(compute_display_rect(1000, 576, false, false), DisplayRect { x: 0, y: 0, width: 1000, height: 576 });
+    }
+}
@@ -5,9 +5,10 @@
 // 転送元: (value << 8) + 0x00 ～ (value << 8) + 0x9F
 // 転送先: 0xFE00 ～ 0xFE9F (OAM)
 // 転送時間: 160 Mサイクル (640 Tサイクル)
-// 転送中はHRAM以外のメモリアクセスが制限される（簡易実装では即時コピー）
+// 転送中はHRAMとDMAレジスタ以外のメモリアクセスが制限される（Peripheralsでブロック）
 
 /// DMA転送コントローラ
+#[derive(Clone)]
 pub struct Dma {
     /// DMA転送アクティブフラグ
     pub active: bool,
@@ -59,7 +60,7 @@ impl Dma {
         }
 
         // 4Tサイクルごとに1バイト転送（デクリメント前にチェック）
-        let transfer = if self.remaining_cycles % 4 == 0 && self.byte_counter < 160 {
+        let transfer = if self.remaining_cycles.is_multiple_of(4) && self.byte_counter < 160 {
             let src = self.source_address() + self.byte_counter as u16;
             let dst = 0xFE00 + self.byte_counter as u16;
             self.byte_counter += 1;
@@ -78,6 +79,12 @@ impl Dma {
     }
 }
 
+impl Default for Dma {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
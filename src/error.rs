@@ -0,0 +1,112 @@
+// src/error.rs
+// ROM・BootROM読み込み時とCPU実行時のエラー型
+//
+// 利用側が失敗原因で分岐できるよう、文字列ではなく列挙型で返す。
+// ファイル入出力はstd機能でのみ扱う（Io バリアントも同様）。
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+/// ROM/BootROMの読み込みエラー
+#[derive(Debug)]
+pub enum LoadError {
+    /// ファイル読み込みの失敗
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// データが必要なサイズに満たない（ROMヘッダ不足、BootROM 256バイト未満）
+    TooSmall,
+    /// ヘッダ・形式が不正（ROM/RAMサイズコードが範囲外、BootROMサイズ超過）
+    BadHeader,
+    /// 未対応のカートリッジタイプ (0x0147の値、`Cartridge::new_builtin`のみ)
+    UnsupportedMapper(u8),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            LoadError::Io(e) => write!(f, "ファイル読み込みエラー: {}", e),
+            LoadError::TooSmall => write!(f, "データが小さすぎます"),
+            LoadError::BadHeader => write!(f, "ヘッダが不正です"),
+            LoadError::UnsupportedMapper(byte) => {
+                write!(f, "未対応のカートリッジタイプです: 0x{:02X}", byte)
+            }
+        }
+    }
+}
+
+impl core::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            LoadError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for LoadError {
+    fn from(e: std::io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+/// CPU実行時のエラー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuError {
+    /// LR35902に存在しないオペコード（実機ではCPUが停止する。以降のstepも同じエラーを返す）
+    InvalidOpcode { opcode: u8, pc: u16 },
+    /// エミュレータが未対応の命令
+    Unimplemented { opcode: u8, pc: u16 },
+}
+
+impl fmt::Display for CpuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CpuError::InvalidOpcode { opcode, pc } => {
+                write!(f, "不正な命令によりCPUが停止しました: 0x{:02X} at PC=0x{:04X}", opcode, pc)
+            }
+            CpuError::Unimplemented { opcode, pc } => {
+                write!(f, "未実装の命令: 0x{:02X} at PC=0x{:04X}", opcode, pc)
+            }
+        }
+    }
+}
+
+impl core::error::Error for CpuError {}
+
+// 文字列エラーを返す実行ループ（Gameboy::step等）から`?`で使えるようにする
+impl From<CpuError> for String {
+    fn from(e: CpuError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_load_error_from_io() {
+        use std::error::Error;
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let error: LoadError = io.into();
+        assert!(matches!(error, LoadError::Io(_)));
+        assert!(error.source().is_some());
+        assert_eq!(LoadError::UnsupportedMapper(0xFC).to_string(), "未対応のカートリッジタイプです: 0xFC");
+    }
+
+    #[test]
+    fn test_cpu_error_display() {
+        let error = CpuError::InvalidOpcode { opcode: 0xD3, pc: 0xC000 };
+        let message: String = error.into();
+        assert_eq!(message, "不正な命令によりCPUが停止しました: 0xD3 at PC=0xC000");
+        assert_eq!(
+            CpuError::Unimplemented { opcode: 0x10, pc: 0x0150 }.to_string(),
+            "未実装の命令: 0x10 at PC=0x0150"
+        );
+    }
+}
@@ -28,6 +28,7 @@ pub enum JoypadButton {
 }
 
 /// ジョイパッドコントローラ
+#[derive(Clone)]
 pub struct Joypad {
     /// ボタンキー状態 (bit0=A, bit1=B, bit2=Select, bit3=Start, 0=押下)
     button_keys: u8,
@@ -36,7 +37,7 @@ pub struct Joypad {
     /// 選択レジスタ (bit4=方向キー選択, bit5=ボタンキー選択)
     select: u8,
     /// 割り込み要求フラグ
-    pub interrupt_request: bool,
+    interrupt_request: bool,
 }
 
 impl Joypad {
@@ -69,11 +70,16 @@ impl Joypad {
         result
     }
 
-    /// JOYPレジスタへの書き込み（上位2bitの選択のみ有効）
+    /// JOYPレジスタへの書き込み（選択bit4-5のみ有効、他のbitは無視）
     pub fn write(&mut self, value: u8) {
         self.select = value & 0x30;
     }
 
+    /// 割り込み要求を取得してクリア（要求があればtrueを一度だけ返す）
+    pub fn take_interrupt(&mut self) -> bool {
+        core::mem::take(&mut self.interrupt_request)
+    }
+
     /// ボタン押下
     pub fn press(&mut self, button: JoypadButton) {
         let old_state = self.get_current_input();
@@ -124,6 +130,12 @@ impl Joypad {
     }
 }
 
+impl Default for Joypad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,6 +225,23 @@ mod tests {
         assert!(!joypad.interrupt_request);
     }
 
+    #[test]
+    fn test_take_interrupt() {
+        let mut joypad = Joypad::new();
+        joypad.write(0x10); // ボタンキー選択
+
+        assert!(!joypad.take_interrupt());
+        joypad.press(JoypadButton::A);
+
+        // 一度だけtrueを返し、次の要求まではfalse
+        assert!(joypad.take_interrupt());
+        assert!(!joypad.take_interrupt());
+
+        joypad.release(JoypadButton::A);
+        joypad.press(JoypadButton::A);
+        assert!(joypad.take_interrupt());
+    }
+
     #[test]
     fn test_both_groups_unselected() {
         let mut joypad = Joypad::new();
@@ -3,12 +3,16 @@
 #[cfg(feature = "with_sdl")]
 use sdl2::pixels::{Color, PixelFormatEnum};
 #[cfg(feature = "with_sdl")]
+use sdl2::rect::Rect;
+#[cfg(feature = "with_sdl")]
 use sdl2::render::{Canvas, Texture, TextureCreator};
 #[cfg(feature = "with_sdl")]
 use sdl2::video::{Window, WindowContext};
 #[cfg(feature = "with_sdl")]
 use sdl2::{EventPump, Sdl, VideoSubsystem};
 
+pub use crate::display_rect::{compute_display_rect, DisplayRect};
+
 const SCREEN_WIDTH: u32 = 160;
 const SCREEN_HEIGHT: u32 = 144;
 const WINDOW_SCALE: u32 = 4;  // 4倍拡大表示
@@ -18,18 +22,29 @@ pub struct LcdDisplay {
     _video_subsystem: VideoSubsystem,
     canvas: Canvas<Window>,
     event_pump: EventPump,
+    integer_scale: bool,
+    keep_aspect: bool,
+    display_rect: DisplayRect,
 }
 
 impl LcdDisplay {
     pub fn new(title: &str) -> Result<Self, String> {
+        Self::new_scaled(title, WINDOW_SCALE)
+    }
+    
+    // 拡大率を指定してディスプレイを作成（ウィンドウはリサイズ可能）
+    pub fn new_scaled(title: &str, scale: u32) -> Result<Self, String> {
+        let scale = scale.max(1);
+        
         // SDL2初期化
         let sdl_context = sdl2::init()?;
         let video_subsystem = sdl_context.video()?;
         
         // ウィンドウ作成
         let window = video_subsystem
-            .window(title, SCREEN_WIDTH * WINDOW_SCALE, SCREEN_HEIGHT * WINDOW_SCALE)
+            .window(title, SCREEN_WIDTH * scale, SCREEN_HEIGHT * scale)
             .position_centered()
+            .resizable()
             .build()
             .map_err(|e| e.to_string())?;
         
@@ -52,9 +67,30 @@ impl LcdDisplay {
             _video_subsystem: video_subsystem,
             canvas,
             event_pump,
+            integer_scale: true,
+            keep_aspect: true,
+            display_rect: compute_display_rect(SCREEN_WIDTH * scale, SCREEN_HEIGHT * scale, true, true),
         })
     }
     
+    // 整数倍スケーリングの有効/無効
+    pub fn set_integer_scale(&mut self, enabled: bool) {
+        self.integer_scale = enabled;
+        self.update_display_rect();
+    }
+    
+    // アスペクト比維持（レターボックス）の有効/無効
+    pub fn set_keep_aspect(&mut self, enabled: bool) {
+        self.keep_aspect = enabled;
+        self.update_display_rect();
+    }
+    
+    // 現在のウィンドウサイズから描画先矩形を再計算
+    fn update_display_rect(&mut self) {
+        let (width, height) = self.canvas.window().size();
+        self.display_rect = compute_display_rect(width, height, self.integer_scale, self.keep_aspect);
+    }
+    
     // フレームバッファを画面に表示
     pub fn present_frame(&mut self, framebuffer: &[u8; 160 * 144 * 3]) -> Result<(), String> {
         // テクスチャを毎回作成して描画
@@ -71,7 +107,8 @@ impl LcdDisplay {
         self.canvas.clear();
         
         // テクスチャを描画（拡大表示）
-        self.canvas.copy(&texture, None, None)?;
+        let rect = self.display_rect;
+        self.canvas.copy(&texture, None, Some(Rect::new(rect.x, rect.y, rect.width, rect.height)))?;
         
         // 画面に表示
         self.canvas.present();
@@ -145,14 +182,18 @@ impl LcdDisplay {
     
     // イベント処理
     pub fn poll_events(&mut self) -> Vec<LcdEvent> {
-        use sdl2::event::Event;
+        use sdl2::event::{Event, WindowEvent};
         use sdl2::keyboard::Keycode;
         
         let mut events = Vec::new();
+        let mut resized = false;
         
         for event in self.event_pump.poll_iter() {
             match event {
                 Event::Quit { .. } => events.push(LcdEvent::Quit),
+                Event::Window { win_event: WindowEvent::Resized(..) | WindowEvent::SizeChanged(..), .. } => {
+                    resized = true;
+                }
                 Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {
                     events.push(LcdEvent::Quit);
                 }
@@ -170,6 +211,11 @@ impl LcdDisplay {
             }
         }
         
+        // リサイズ時は描画先矩形を再計算
+        if resized {
+            self.update_display_rect();
+        }
+        
         events
     }
     
@@ -251,6 +297,12 @@ impl FpsCounter {
     }
 }
 
+impl Default for FpsCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -280,4 +332,4 @@ mod tests {
         // 実際のFPS計算は時間に依存するため、値の範囲のみテスト
         assert!(counter.fps() >= 0.0);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,105 @@
+// src/logger.rs
+// 診断メッセージ用の軽量ロギング機構
+//
+// 各コンポーネントはprintln!で直接出力せず、利用側が設定したシンクへ記録を渡す。
+// シンク未設定時は何も出力しない（メッセージの組み立ても行わない）。
+
+use alloc::{boxed::Box, string::String};
+
+/// ログレベル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+}
+
+/// ログ記録
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// ログ記録を受け取るシンク
+pub type LogSink = Box<dyn FnMut(&LogRecord)>;
+
+/// 標準出力へ書き出すシンクを作成
+#[cfg(feature = "std")]
+pub fn stdout_sink() -> LogSink {
+    Box::new(|record| println!("[{:?}] {}", record.level, record.message))
+}
+
+/// ロガー（シンクを1つ保持）
+pub struct Logger {
+    sink: Option<LogSink>,
+}
+
+impl Logger {
+    pub fn new() -> Self {
+        Self { sink: None }
+    }
+
+    /// シンクを設定
+    pub fn set_sink(&mut self, sink: LogSink) {
+        self.sink = Some(sink);
+    }
+
+    /// シンクを解除
+    pub fn clear_sink(&mut self) {
+        self.sink = None;
+    }
+
+    /// シンクが設定されているかどうか
+    pub fn is_enabled(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    /// ログを記録（シンク未設定時はメッセージを生成しない）
+    pub fn log(&mut self, level: LogLevel, message: impl FnOnce() -> String) {
+        if let Some(sink) = self.sink.as_mut() {
+            sink(&LogRecord { level, message: message() });
+        }
+    }
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_logger_without_sink() {
+        let mut logger = Logger::new();
+        assert!(!logger.is_enabled());
+        // シンク未設定ならメッセージは生成されない
+        logger.log(LogLevel::Info, || unreachable!());
+    }
+
+    #[test]
+    fn test_logger_capture() {
+        let records = Rc::new(RefCell::new(Vec::new()));
+        let captured = Rc::clone(&records);
+
+        let mut logger = Logger::new();
+        logger.set_sink(Box::new(move |record| captured.borrow_mut().push(record.clone())));
+        logger.log(LogLevel::Warn, || "テスト".to_string());
+
+        assert_eq!(
+            *records.borrow(),
+            vec![LogRecord { level: LogLevel::Warn, message: "テスト".to_string() }]
+        );
+
+        logger.clear_sink();
+        logger.log(LogLevel::Warn, || "無視".to_string());
+        assert_eq!(records.borrow().len(), 1);
+    }
+}
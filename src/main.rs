@@ -1,21 +1,11 @@
 // src/main.rs - メモリマップ対応版
 use std::env;
-use std::fs;
 
-mod memory_map;      // メモリマップ定義
-mod memory;          // メモリコンポーネント
-mod peripherals;     // メモリバス
-mod cpu;             // CPUコンポーネント
-mod ppu;             // PPUコンポーネント
-mod simple_display;  // 簡易ASCII表示
-mod joypad;          // ジョイパッド入力
-mod dma;             // DMA転送コントローラ
-mod cartridge;       // カートリッジ・MBCシステム
-mod serial;          // シリアル通信
-mod apu;             // APU（音声処理ユニット）
+// エミュレーションコアはライブラリ (src/lib.rs) 側で定義
+use rustboy::{memory_map, memory, peripherals, cpu, ppu, simple_display, logger};
 
 #[cfg(feature = "with_sdl")]
-mod lcd;             // LCDディスプレイ
+use rustboy::lcd;             // LCDディスプレイ
 
 use memory::BootRom;
 use peripherals::Peripherals;
@@ -55,22 +45,13 @@ fn main() {
 fn load_bootrom_from_file(bootrom_path: &str) {
     println!("BootROMファイルを読み込み中: {}", bootrom_path);
     
-    match fs::read(bootrom_path) {
-        Ok(data) => {
-            match BootRom::new(data.into_boxed_slice()) {
-                Ok(bootrom) => {
-                    println!("✓ BootROM読み込み成功");
-                    test_memory_system(bootrom);
-                }
-                Err(e) => {
-                    eprintln!("✗ BootROM作成エラー: {}", e);
-                    println!("ダミーBootROMでテストを続行...\n");
-                    test_with_dummy_bootrom();
-                }
-            }
+    match BootRom::from_file(bootrom_path) {
+        Ok(bootrom) => {
+            println!("✓ BootROM読み込み成功");
+            test_memory_system(bootrom);
         }
         Err(e) => {
-            eprintln!("✗ ファイル読み込みエラー: {}", e);
+            eprintln!("✗ BootROM読み込みエラー: {}", e);
             println!("ダミーBootROMでテストを続行...\n");
             test_with_dummy_bootrom();
         }
@@ -85,6 +66,7 @@ fn test_with_dummy_bootrom() {
 
 fn test_memory_system(bootrom: BootRom) {
     let mut peripherals = Peripherals::new(bootrom);
+    peripherals.set_log_sink(logger::stdout_sink());
     
     println!("\n=== メモリシステムテスト（メモリマップ対応版） ===");
     
@@ -1,4 +1,8 @@
+use alloc::{boxed::Box, format, string::String, vec};
+#[cfg(feature = "std")]
+use crate::error::LoadError;
 use crate::memory_map::dmg::{BOOTROM_SIZE, BOOTROM_START, BOOTROM_END};
+#[derive(Clone)]
 pub struct BootRom {
     data: Box<[u8]>,
     active: bool,
@@ -21,6 +25,22 @@ impl BootRom {
         })
     }
 
+    /// ファイルからBootROMを読み込む
+    #[cfg(feature = "std")]
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, LoadError> {
+        let data = std::fs::read(path)?;
+        if data.len() < BOOTROM_SIZE {
+            return Err(LoadError::TooSmall);
+        }
+        if data.len() > BOOTROM_SIZE {
+            return Err(LoadError::BadHeader);
+        }
+        Ok(BootRom {
+            data: data.into_boxed_slice(),
+            active: true,
+        })
+    }
+
     pub fn new_dummy() -> Self {
         let mut data = vec![0x00; BOOTROM_SIZE];
 
@@ -40,7 +60,7 @@ impl BootRom {
             return 0xFF; // BootRomが非アクティブな場合は常に0xFFを返す
         }
 
-        if addr < BOOTROM_START || addr > BOOTROM_END {
+        if !(BOOTROM_START..=BOOTROM_END).contains(&addr) {
             return 0xFF; // BootRomの範囲外は常に0xFFを返す
         }
 
@@ -51,11 +71,13 @@ impl BootRom {
         self.active
     }
 
-    pub fn write_disable_register(&mut self, value: u8) {
-        if value != 0 {
+    /// 無効化レジスタへの書き込み。この書き込みで非アクティブになった場合はtrueを返す
+    pub fn write_disable_register(&mut self, value: u8) -> bool {
+        if value != 0 && self.active {
             self.active = false; // 0以外の値が書き込まれたらBootRomを非アクティブにする
-            println!("0以外の値 0x{:02X} がBootRomに書き込まれました。BootRomを非アクティブにします。", value);
+            return true;
         }
+        false
     }
 
     pub fn dump(&self) -> String {
@@ -95,6 +117,19 @@ mod tests {
         assert!(result.is_err());
     }
     
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_bootrom_from_file_errors() {
+        let missing = std::env::temp_dir().join("rustboy_missing_bootrom.bin");
+        assert!(matches!(BootRom::from_file(&missing), Err(LoadError::Io(_))));
+
+        let short = std::env::temp_dir().join(format!("rustboy_short_bootrom_{}.bin", std::process::id()));
+        std::fs::write(&short, [0u8; 100]).unwrap();
+        let result = BootRom::from_file(&short);
+        std::fs::remove_file(&short).unwrap();
+        assert!(matches!(result, Err(LoadError::TooSmall)));
+    }
+    
     #[test]
     fn test_bootrom_read() {
         let mut data = vec![0u8; 256];
@@ -110,8 +145,10 @@ mod tests {
         let mut bootrom = BootRom::new(data.into_boxed_slice()).unwrap();
         
         assert!(bootrom.is_active());
-        bootrom.write_disable_register(1);
+        assert!(bootrom.write_disable_register(1));
         assert!(!bootrom.is_active());
+        // 既に無効化済みならfalse
+        assert!(!bootrom.write_disable_register(1));
         
         // 無効化後は0xFFを返す
         assert_eq!(bootrom.read(0x00), 0xFF);
@@ -2,8 +2,11 @@
 // High RAM: CPUが高速にアクセスできる127バイトの小さなメモリ
 // スタック操作や重要な変数の保存に使用される
 
+use alloc::{boxed::Box, format, string::String};
+use super::MemPattern;
 use crate::memory_map::dmg::{HRAM_SIZE, HRAM_START, HRAM_END};
 
+#[derive(Clone)]
 pub struct HighRam {
     data: Box<[u8; HRAM_SIZE]>,
 }
@@ -15,6 +18,13 @@ impl HighRam {
             data: Box::new([0; HRAM_SIZE]),
         }
     }
+
+    /// 指定パターンで初期化したHigh RAMを作成
+    pub fn new_with_pattern(pattern: MemPattern) -> Self {
+        let mut ram = Self::new();
+        pattern.fill(&mut ram.data[..]);
+        ram
+    }
     
     /// 指定されたアドレスからデータを読み取る
     pub fn read(&self, addr: u16) -> u8 {
@@ -33,7 +43,7 @@ impl HighRam {
     fn addr_to_index(&self, addr: u16) -> usize {
         // アドレス範囲チェック（デバッグビルドでのみ）
         debug_assert!(
-            addr >= HRAM_START && addr <= HRAM_END,
+            (HRAM_START..=HRAM_END).contains(&addr),
             "HRAMアドレス範囲外: 0x{:04X} (有効範囲: 0x{:04X}-0x{:04X})",
             addr, HRAM_START, HRAM_END
         );
@@ -47,8 +57,10 @@ impl HighRam {
         self.data.fill(0);
     }
     
-    /// スタック操作のヘルパー関数
-    /// GameBoyのスタックは通常HRAMの上位部分を使用する
+    /// HRAM内に限定したスタック操作のヘルパー関数（単体テスト用の簡易版）
+    ///
+    /// CPUのPUSH/POP/CALL/RETはこれを使わず、任意のSPに対してバス経由で読み書きする
+    /// （実際のゲームのスタックは多くがWRAMに置かれる）
     pub fn push_stack(&mut self, sp: &mut u16, value: u8) -> Result<(), String> {
         if *sp < HRAM_START {
             return Err(format!("スタックポインタが範囲外: 0x{:04X}", sp));
@@ -100,7 +112,7 @@ impl HighRam {
                 let addr = base_addr + col;
                 if addr <= HRAM_END {
                     let value = self.read(addr);
-                    if value >= 32 && value <= 126 {
+                    if (32..=126).contains(&value) {
                         result.push(value as char);
                     } else {
                         result.push('.');
@@ -10,6 +10,47 @@ pub use bootrom::BootRom;
 pub use wram::WorkRam;
 pub use hram::HighRam;
 
+/// 未初期化メモリの初期値パターン
+///
+/// 実機の電源投入直後のRAMは不定値のため、常に0だと隠れるバグの検出に使う
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemPattern {
+    /// 全て0x00
+    Zero,
+    /// 全て0xFF
+    Ones,
+    /// シード付き線形合同法による疑似乱数（同じシードなら常に同じ内容）
+    Lcg(u32),
+}
+
+impl MemPattern {
+    /// 領域の先頭アドレスを混ぜたパターンを返す（Lcgのみ、他はそのまま）
+    ///
+    /// 同じシードでもWRAM/HRAM/VRAM/OAMが同一の乱数列で始まらないようにする
+    pub fn for_region(self, base: u16) -> Self {
+        match self {
+            MemPattern::Lcg(seed) => MemPattern::Lcg(seed ^ (base as u32).wrapping_mul(0x9E37_79B9)),
+            other => other,
+        }
+    }
+
+    /// バッファをパターンで埋める
+    pub fn fill(self, buffer: &mut [u8]) {
+        match self {
+            MemPattern::Zero => buffer.fill(0x00),
+            MemPattern::Ones => buffer.fill(0xFF),
+            MemPattern::Lcg(seed) => {
+                let mut state = seed;
+                for byte in buffer.iter_mut() {
+                    state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                    // 下位ビットは周期が短いので上位側を使う
+                    *byte = (state >> 16) as u8;
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::memory_map::dmg::*;
@@ -28,6 +69,34 @@ mod tests {
         assert_eq!(HRAM_END - HRAM_START + 1, HRAM_SIZE as u16);
     }
     
+    #[test]
+    fn test_mem_pattern_fill() {
+        use super::MemPattern;
+
+        let mut a = [0u8; 256];
+        let mut b = [0u8; 256];
+        MemPattern::Lcg(0x1234).fill(&mut a);
+        MemPattern::Lcg(0x1234).fill(&mut b);
+        // 同じシードなら再現可能、かつ全0ではない
+        assert_eq!(a, b);
+        assert!(a.iter().any(|&x| x != 0));
+        // 値が偏っていない（256バイト中で十分な種類の値が出る）
+        let mut seen = [false; 256];
+        for &x in &a {
+            seen[x as usize] = true;
+        }
+        assert!(seen.iter().filter(|&&s| s).count() > 100);
+
+        // シードが違えば内容も違う
+        MemPattern::Lcg(0x1235).fill(&mut b);
+        assert_ne!(a, b);
+
+        MemPattern::Ones.fill(&mut a);
+        assert!(a.iter().all(|&x| x == 0xFF));
+        MemPattern::Zero.fill(&mut a);
+        assert!(a.iter().all(|&x| x == 0x00));
+    }
+
     #[test]
     fn test_memory_region_detection() {
         assert_eq!(get_memory_region(0x0000), MemoryRegion::BootRom);
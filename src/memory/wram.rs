@@ -1,8 +1,11 @@
 // src/memory/wram.rs
 // Work RAM: ゲームが作業用に使用する8KBのメモリ
 
+use alloc::{boxed::Box, format, string::String};
+use super::MemPattern;
 use crate::memory_map::dmg::{WRAM_SIZE, WRAM_START, WRAM_END};
 
+#[derive(Clone)]
 pub struct WorkRam {
     data: Box<[u8; WRAM_SIZE]>,
 }
@@ -14,6 +17,13 @@ impl WorkRam {
             data: Box::new([0; WRAM_SIZE]),
         }
     }
+
+    /// 指定パターンで初期化したWork RAMを作成
+    pub fn new_with_pattern(pattern: MemPattern) -> Self {
+        let mut ram = Self::new();
+        pattern.fill(&mut ram.data[..]);
+        ram
+    }
     
     /// 指定されたアドレスからデータを読み取る
     pub fn read(&self, addr: u16) -> u8 {
@@ -32,7 +42,7 @@ impl WorkRam {
     fn addr_to_index(&self, addr: u16) -> usize {
         // アドレス範囲チェック（デバッグビルドでのみ）
         debug_assert!(
-            addr >= WRAM_START && addr <= WRAM_END,
+            (WRAM_START..=WRAM_END).contains(&addr),
             "WRAMアドレス範囲外: 0x{:04X} (有効範囲: 0x{:04X}-0x{:04X})",
             addr, WRAM_START, WRAM_END
         );
@@ -44,7 +54,7 @@ impl WorkRam {
     /// メモリの特定の範囲をクリア
     pub fn clear_range(&mut self, start_addr: u16, end_addr: u16) {
         for addr in start_addr..=end_addr {
-            if addr >= WRAM_START && addr <= WRAM_END {
+            if (WRAM_START..=WRAM_END).contains(&addr) {
                 self.write(addr, 0);
             }
         }
@@ -67,7 +77,7 @@ impl WorkRam {
             
             for i in 0..16 {
                 let current_addr = addr + i;
-                if current_addr >= WRAM_START && current_addr <= WRAM_END && current_addr <= end_addr {
+                if (WRAM_START..=WRAM_END).contains(&current_addr) && current_addr <= end_addr {
                     let value = self.read(current_addr);
                     if current_addr >= start_addr {
                         result.push_str(&format!("{:02X} ", value));
@@ -84,10 +94,10 @@ impl WorkRam {
             // ASCII表示
             for i in 0..16 {
                 let current_addr = addr + i;
-                if current_addr >= WRAM_START && current_addr <= WRAM_END && 
+                if (WRAM_START..=WRAM_END).contains(&current_addr) && 
                    current_addr <= end_addr && current_addr >= start_addr {
                     let value = self.read(current_addr);
-                    if value >= 32 && value <= 126 {
+                    if (32..=126).contains(&value) {
                         result.push(value as char);
                     } else {
                         result.push('.');
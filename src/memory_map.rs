@@ -13,6 +13,8 @@
 //! このファイルはGameBoyのメモリマップを一元管理します。
 //! 全てのアドレス範囲、サイズ、特別なレジスタアドレスを定義。
 
+use alloc::{format, string::String};
+
 /// GameBoy DMG (オリジナル) のメモリマップ
 pub mod dmg {
     // ===== BootROM =====
@@ -52,6 +54,7 @@ pub mod dmg {
     pub const WRAM_SIZE: usize = 0x2000;    // 8KB
     
     // ===== Work RAM Echo (使用禁止) =====
+    // 0xC000-0xDDFFのみをミラーする（0xFE00以降はOAMのため、WRAM上位0xDE00-0xDFFFにはエコーがない）
     pub const WRAM_ECHO_START: u16 = 0xE000;
     pub const WRAM_ECHO_END: u16 = 0xFDFF;
     
@@ -260,6 +263,7 @@ pub fn get_address_info(addr: u16) -> String {
 }
 
 /// メモリマップ全体を表示
+#[cfg(feature = "std")]
 pub fn print_memory_map() {
     println!("=== GameBoy DMG Memory Map ===");
     println!("0x0000-0x00FF: BootROM (256B)");
@@ -275,6 +279,7 @@ pub fn print_memory_map() {
     println!("0xFFFF:        Interrupt Enable (1B)");
 }
 
+#[cfg(feature = "std")]
 pub fn analyze_address(addr: u16) {
     println!("=== Address Analysis: 0x{:04X} ===", addr);
     println!("Region: {}", get_region_name(addr));
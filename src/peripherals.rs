@@ -1,18 +1,63 @@
+use alloc::collections::BTreeMap;
 use crate::memory_map::{
     dmg::*,
     io_registers::*,
-    get_region_name,
+    get_region_name, get_io_register_name,
 };
 use crate::memory::{
-    BootRom, WorkRam, HighRam,
+    BootRom, WorkRam, HighRam, MemPattern,
 };
-use crate::ppu::Ppu;
+use crate::ppu::{Ppu, vram::Vram, registers::{lcdc, stat}};
 use crate::cpu::timer::Timer;
 use crate::joypad::Joypad;
 use crate::dma::Dma;
 use crate::cartridge::Cartridge;
 use crate::serial::Serial;
 use crate::apu::Apu;
+use crate::logger::{LogLevel, LogSink, Logger};
+use core::ops::RangeInclusive;
+use alloc::{format, string::String, vec::Vec};
+
+/// ウォッチポイントの監視対象となるアクセス種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    /// 読み取りのみ
+    Read,
+    /// 書き込みのみ
+    Write,
+    /// 読み書き両方
+    ReadWrite,
+}
+
+impl WatchKind {
+    fn matches(self, is_write: bool) -> bool {
+        match self {
+            WatchKind::Read => !is_write,
+            WatchKind::Write => is_write,
+            WatchKind::ReadWrite => true,
+        }
+    }
+}
+
+/// ウォッチポイントにヒットしたアクセスの情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchpointHit {
+    /// アクセスされたアドレス
+    pub addr: u16,
+    /// 書き込みならtrue
+    pub is_write: bool,
+    /// 読み書きされた値
+    pub value: u8,
+}
+
+/// 未実装I/Oレジスタへのアクセス記録（監査モード用）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnhandledIoAccess {
+    /// アクセス回数（読み書きの合計）
+    pub count: u64,
+    /// 最後に読み書きされた値
+    pub last_value: u8,
+}
 
 pub struct Peripherals {
     bootrom: BootRom,
@@ -28,11 +73,48 @@ pub struct Peripherals {
 
     // 割り込みレジスタ
     pub interrupt_flag: u8,     // IF (0xFF0F)
-    pub interrupt_enable: u8,   // IE (0xFFFF)
+    pub interrupt_enable: u8,   // IE (0xFFFF) 全8bit読み書き可、割り込み判定は下位5bitのみ
 
-    // 統計情報
+    // 統計情報（read/write_countはバスアクセス=バイト単位、16bitアクセスは1回として別に数える）
     read_count: u64,
     write_count: u64,
+    read16_count: u64,
+    write16_count: u64,
+    // falseなら統計カウンタと未実装I/Oの診断出力を完全に省略する（ベンチマーク用）
+    stats_enabled: bool,
+
+    // 監査モード（未実装I/Oレジスタへのアクセスを記録）
+    audit_mode: bool,
+    unhandled_io: BTreeMap<u16, UnhandledIoAccess>,
+
+    // 共有タイル領域($8800-$8FFF)への書き込みをログに出すか
+    log_shared_tile_writes: bool,
+
+    // ウォッチポイント
+    watchpoints: Vec<(RangeInclusive<u16>, WatchKind)>,
+    watchpoint_hit: Option<WatchpointHit>,
+
+    // 診断メッセージの出力先
+    logger: Logger,
+}
+
+/// Peripheralsのエミュレーション状態のスナップショット（セーブステート用）
+///
+/// 統計・監査記録・ウォッチポイント・ログ出力先・独自マッパーなどの設定は含まない
+#[derive(Clone)]
+pub struct PeripheralsState {
+    bootrom: BootRom,
+    wram: WorkRam,
+    hram: HighRam,
+    ppu: Ppu,
+    timer: Timer,
+    joypad: Joypad,
+    dma: Dma,
+    cartridge: Option<Cartridge>,
+    serial: Serial,
+    apu: Apu,
+    interrupt_flag: u8,
+    interrupt_enable: u8,
 }
 
 impl Peripherals {
@@ -53,30 +135,163 @@ impl Peripherals {
             interrupt_enable: 0x00,
             read_count: 0,
             write_count: 0,
+            read16_count: 0,
+            write16_count: 0,
+            stats_enabled: true,
+            audit_mode: false,
+            unhandled_io: BTreeMap::new(),
+            log_shared_tile_writes: false,
+            watchpoints: Vec::new(),
+            watchpoint_hit: None,
+            logger: Logger::new(),
         }
     }
 
+    /// WRAM/HRAM/VRAM/OAMを指定パターンで初期化したPeripheralsを作成（Lcgは領域ごとにシードを変える）
+    pub fn new_with_pattern(bootrom: BootRom, pattern: MemPattern) -> Self {
+        let mut peripherals = Self::new(bootrom);
+        peripherals.wram = WorkRam::new_with_pattern(pattern.for_region(WRAM_START));
+        peripherals.hram = HighRam::new_with_pattern(pattern.for_region(HRAM_START));
+        peripherals.ppu = Ppu::new_with_pattern(pattern);
+        peripherals
+    }
+
     /// ダミーBootROMでPeripheralsを作成（テスト用）
     pub fn new_with_dummy_bootrom() -> Self {
         Self::new(BootRom::new_dummy())
     }
 
-    /// カートリッジをセット
+    /// カートリッジをセット（ヘッダ記載のROMサイズと実サイズが異なる場合は警告を記録）
     pub fn load_cartridge(&mut self, cartridge: Cartridge) {
+        let (declared, actual) = cartridge.declared_vs_actual_rom();
+        if declared != actual {
+            self.logger.log(LogLevel::Warn, || {
+                format!(
+                    "ROMサイズがヘッダと一致しません: ヘッダ {} バイト / 実データ {} バイト（不完全なダンプの可能性）",
+                    declared, actual
+                )
+            });
+        }
         self.cartridge = Some(cartridge);
     }
 
+    /// BootROM実行後の状態にする（BootROM無効化、DIV/IF/LCDC/BGPを起動後の値に設定）
+    pub fn apply_post_boot_state(&mut self) {
+        self.bootrom.write_disable_register(0x01);
+        self.timer = Timer::new_post_boot();
+        self.interrupt_flag = 0x01;
+        self.ppu.registers.lcdc = lcdc::LCD_ENABLE | lcdc::TILE_DATA | lcdc::BG_ENABLE;
+        self.ppu.registers.bgp = 0xFC;
+    }
+
+    /// 診断メッセージのシンクを設定
+    pub fn set_log_sink(&mut self, sink: LogSink) {
+        self.logger.set_sink(sink);
+    }
+
+    /// 監査モードの有効/無効を切り替え
+    ///
+    /// 有効な間は未実装I/Oレジスタへのアクセスを記録する（set_stats_enabled(false)中も記録する）
+    pub fn set_audit_mode(&mut self, enabled: bool) {
+        self.audit_mode = enabled;
+    }
+
+    /// 共有タイル領域($8800-$8FFF、BG符号付き/符号なし両方から参照)への書き込みログの有効/無効を切り替え
+    pub fn set_log_shared_tile_writes(&mut self, enabled: bool) {
+        self.log_shared_tile_writes = enabled;
+    }
+
+    /// 統計情報と診断出力の有効/無効を切り替え（デフォルト有効）
+    ///
+    /// 無効にするとread/writeのカウンタ更新と未実装I/Oのログを省略し、
+    /// スループット重視の実行でバスアクセスのオーバーヘッドを減らす。
+    /// 監査記録はset_audit_modeだけで制御され、この設定の影響を受けない
+    pub fn set_stats_enabled(&mut self, enabled: bool) {
+        self.stats_enabled = enabled;
+    }
+
+    /// 統計情報が有効かどうか
+    pub fn is_stats_enabled(&self) -> bool {
+        self.stats_enabled
+    }
+
+    /// 監査モードが有効かどうか
+    pub fn is_audit_mode(&self) -> bool {
+        self.audit_mode
+    }
+
+    /// 未実装I/Oレジスタへのアクセス回数をアドレス順に取得
+    pub fn unhandled_io_report(&self) -> Vec<(u16, u64)> {
+        self.unhandled_io
+            .iter()
+            .map(|(&addr, access)| (addr, access.count))
+            .collect()
+    }
+
+    /// 未実装I/Oレジスタへのアクセス記録の詳細を取得
+    pub fn unhandled_io_access(&self, addr: u16) -> Option<UnhandledIoAccess> {
+        self.unhandled_io.get(&addr).copied()
+    }
+
+    /// 未実装I/Oレジスタへのアクセス記録をクリア（reset_statsでは消えない）
+    pub fn clear_unhandled_io_report(&mut self) {
+        self.unhandled_io.clear();
+    }
+
+    /// 未実装I/Oレジスタへのアクセスを記録（監査モード時のみ）
+    fn record_unhandled_io(&mut self, addr: u16, value: u8) {
+        if !self.audit_mode {
+            return;
+        }
+        let access = self.unhandled_io.entry(addr).or_default();
+        access.count += 1;
+        access.last_value = value;
+    }
+
+    /// ウォッチポイントを追加
+    pub fn add_watchpoint(&mut self, range: RangeInclusive<u16>, kind: WatchKind) {
+        self.watchpoints.push((range, kind));
+    }
+
+    /// 全ウォッチポイントを削除
+    pub fn clear_watchpoints(&mut self) {
+        self.watchpoints.clear();
+        self.watchpoint_hit = None;
+    }
+
+    /// ウォッチポイントのヒット情報を取得してクリア
+    pub fn take_watchpoint_hit(&mut self) -> Option<WatchpointHit> {
+        self.watchpoint_hit.take()
+    }
+
+    /// アクセスがウォッチポイントに該当するか確認（最初のヒットのみ保持）
+    fn check_watchpoints(&mut self, addr: u16, is_write: bool, value: u8) {
+        if self.watchpoint_hit.is_some() {
+            return;
+        }
+        let hit = self
+            .watchpoints
+            .iter()
+            .any(|(range, kind)| range.contains(&addr) && kind.matches(is_write));
+        if hit {
+            self.watchpoint_hit = Some(WatchpointHit { addr, is_write, value });
+        }
+    }
+
     /// CPUサイクルに同期してPPU/Timer/DMA/Serial/APU/Cartridgeを進める
-    pub fn tick(&mut self, cycles: u8) {
+    ///
+    /// 戻り値: この間にPPUがVBlankに入った場合true
+    pub fn tick(&mut self, cycles: u8) -> bool {
+        let mut vblank = false;
         for _ in 0..cycles {
-            self.ppu.step();
-            self.timer.tick();
-            self.serial.tick();
-            self.apu.tick();
+            vblank |= self.ppu.step();
 
-            // カートリッジRTCティック
-            if let Some(ref mut cart) = self.cartridge {
-                cart.tick();
+            // PPUの割り込みフラグは遷移したサイクルで取り出してIFに反映
+            if self.ppu.take_vblank_interrupt() {
+                self.interrupt_flag |= 0x01; // VBlank割り込み (bit 0)
+            }
+            if self.ppu.take_stat_interrupt() {
+                self.interrupt_flag |= 0x02; // STAT割り込み (bit 1)
             }
 
             // DMA転送処理
@@ -89,33 +304,51 @@ impl Peripherals {
             }
         }
 
-        // PPUの割り込みフラグをIFに反映
-        if self.ppu.vblank_interrupt {
-            self.interrupt_flag |= 0x01; // VBlank割り込み (bit 0)
-            self.ppu.vblank_interrupt = false;
-        }
-        if self.ppu.stat_interrupt {
-            self.interrupt_flag |= 0x02; // STAT割り込み (bit 1)
-            self.ppu.stat_interrupt = false;
+        // Timer/Serial/APU/カートリッジはPPU・DMAと相互作用しないためまとめて進める
+        self.timer.tick(cycles as u32);
+        self.serial.tick(cycles as u32);
+        self.apu.tick(cycles as u32);
+        if let Some(ref mut cart) = self.cartridge {
+            cart.tick(cycles as u32);
         }
 
-        // Timerの割り込みフラグをIFに反映
-        if self.timer.interrupt_request {
+        // Timerの割り込み要求をIFに反映
+        if self.timer.take_interrupt() {
             self.interrupt_flag |= 0x04; // Timer割り込み (bit 2)
-            self.timer.interrupt_request = false;
         }
 
-        // Serialの割り込みフラグをIFに反映
-        if self.serial.interrupt_request {
+        // Serialの割り込み要求をIFに反映
+        if self.serial.take_interrupt() {
             self.interrupt_flag |= 0x08; // Serial割り込み (bit 3)
-            self.serial.interrupt_request = false;
         }
 
-        // Joypadの割り込みフラグをIFに反映
-        if self.joypad.interrupt_request {
+        // Joypadの割り込み要求をIFに反映
+        if self.joypad.take_interrupt() {
             self.interrupt_flag |= 0x10; // Joypad割り込み (bit 4)
-            self.joypad.interrupt_request = false;
         }
+
+        vblank
+    }
+
+    /// PPU/Timer/Serialのいずれかが次に割り込みを要求しうるまでのサイクル数（発生源がなければNone）
+    ///
+    /// この値より前には新たな割り込みが要求されない（Joypadは外部入力のため含まない）
+    pub fn cycles_until_next_event(&self) -> Option<u32> {
+        [
+            self.ppu.cycles_until_mode_change(),
+            self.timer.cycles_until_interrupt(),
+            self.serial.cycles_until_interrupt(),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+    }
+
+    /// OAM DMA転送中にCPUからアクセスできないアドレスかどうか
+    ///
+    /// DMAレジスタは転送中でも書き込めるため、転送を途中から再開始できる
+    fn is_blocked_by_dma(&self, addr: u16) -> bool {
+        self.dma.is_active() && !matches!(addr, HRAM_START..=HRAM_END | IE_REGISTER | IF | DMA)
     }
 
     /// DMA転送用の読み取り（OAMを除く全メモリからの読み取り）
@@ -156,9 +389,14 @@ impl Peripherals {
     
     /// 指定されたアドレスからデータを読み取る
     pub fn read(&mut self, addr: u16) -> u8 {
-        self.read_count += 1;
+        if self.stats_enabled {
+            self.read_count += 1;
+        }
 
         let value = match addr {
+            // OAM DMA転送中はHRAMとIE/IF/DMA以外読めない
+            _ if self.is_blocked_by_dma(addr) => 0xFF,
+
             // BootROM領域
             BOOTROM_START..=BOOTROM_END => {
                 if self.bootrom.is_active() {
@@ -229,13 +467,21 @@ impl Peripherals {
         };
 
         #[cfg(feature = "trace_memory")]
-        println!("READ  0x{:04X} = 0x{:02X} [{}]", addr, value, get_region_name(addr));
+        if self.stats_enabled {
+            self.logger.log(LogLevel::Trace, || {
+                format!("READ  0x{:04X} = 0x{:02X} [{}]", addr, value, get_region_name(addr))
+            });
+        }
+
+        if !self.watchpoints.is_empty() {
+            self.check_watchpoints(addr, false, value);
+        }
 
         value
     }
 
     /// I/Oレジスタの読み取り
-    fn read_io(&self, addr: u16) -> u8 {
+    fn read_io(&mut self, addr: u16) -> u8 {
         match addr {
             // ジョイパッド
             JOYP => self.joypad.read(),
@@ -248,13 +494,13 @@ impl Peripherals {
             LCDC => self.ppu.registers.lcdc,
             STAT => {
                 // STATの下位3bitはPPU状態から構成
-                let mode = self.ppu.mode as u8;
-                let lyc_flag = if self.ppu.scanline == self.ppu.registers.lyc { 0x04 } else { 0x00 };
-                (self.ppu.registers.stat & 0xF8) | lyc_flag | mode
+                let mode = self.ppu.stat_mode();
+                let lyc_flag = if self.ppu.ly() == self.ppu.registers.lyc { stat::LYC_EQUAL } else { 0x00 };
+                (self.ppu.registers.stat & !stat::READ_ONLY_MASK) | lyc_flag | mode
             }
             SCY => self.ppu.registers.scy,
             SCX => self.ppu.registers.scx,
-            LY => self.ppu.scanline,
+            LY => self.ppu.ly(),
             LYC => self.ppu.registers.lyc,
             DMA => self.dma.read(),
             BGP => self.ppu.registers.bgp,
@@ -280,8 +526,10 @@ impl Peripherals {
 
             // その他のI/Oレジスタ（未実装）
             _ => {
-                #[cfg(feature = "trace_memory")]
-                println!("未実装I/Oレジスタ読み取り: 0x{:04X}", addr);
+                if self.stats_enabled {
+                    self.logger.log(LogLevel::Debug, || format!("未実装I/Oレジスタ読み取り: 0x{:04X}", addr));
+                }
+                self.record_unhandled_io(addr, 0xFF);
                 0xFF
             }
         }
@@ -289,12 +537,25 @@ impl Peripherals {
     
     /// 指定されたアドレスにデータを書き込む
     pub fn write(&mut self, addr: u16, value: u8) {
-        self.write_count += 1;
+        if self.stats_enabled {
+            self.write_count += 1;
+        }
 
         #[cfg(feature = "trace_memory")]
-        println!("WRITE 0x{:04X} = 0x{:02X} [{}]", addr, value, get_region_name(addr));
+        if self.stats_enabled {
+            self.logger.log(LogLevel::Trace, || {
+                format!("WRITE 0x{:04X} = 0x{:02X} [{}]", addr, value, get_region_name(addr))
+            });
+        }
+
+        if !self.watchpoints.is_empty() {
+            self.check_watchpoints(addr, true, value);
+        }
 
         match addr {
+            // OAM DMA転送中はHRAMとIE/IF/DMA以外への書き込みを無視
+            _ if self.is_blocked_by_dma(addr) => {}
+
             // BootROM/カートリッジROM Bank 0 領域（MBCレジスタ操作）
             BOOTROM_START..=BOOTROM_END => {
                 if let Some(ref mut cart) = self.cartridge {
@@ -311,6 +572,17 @@ impl Peripherals {
 
             // VRAM領域
             VRAM_START..=VRAM_END => {
+                if self.log_shared_tile_writes && Vram::is_shared_region(addr - VRAM_START) {
+                    self.logger.log(LogLevel::Debug, || {
+                        format!(
+                            "共有タイル領域への書き込み: 0x{:04X} = 0x{:02X} (タイル{} / 符号付きID {})",
+                            addr,
+                            value,
+                            (addr - VRAM_START) / 16,
+                            ((addr - VRAM_START) / 16) as u8 as i8
+                        )
+                    });
+                }
                 self.ppu.write_vram(addr, value);
             }
 
@@ -368,10 +640,10 @@ impl Peripherals {
             SC => self.serial.write_sc(value),
 
             // PPUレジスタ
-            LCDC => self.ppu.registers.lcdc = value,
+            LCDC => self.ppu.set_lcdc(value),
             STAT => {
                 // STATの下位3bitは読み取り専用（PPU状態）
-                self.ppu.registers.stat = (value & 0xF8) | (self.ppu.registers.stat & 0x07);
+                self.ppu.registers.stat = (value & !stat::READ_ONLY_MASK) | (self.ppu.registers.stat & stat::READ_ONLY_MASK);
             }
             SCY => self.ppu.registers.scy = value,
             SCX => self.ppu.registers.scx = value,
@@ -400,26 +672,89 @@ impl Peripherals {
 
             // BootROM無効化レジスタ
             BOOTROM_DISABLE => {
-                self.bootrom.write_disable_register(value);
+                if self.bootrom.write_disable_register(value) {
+                    self.logger.log(LogLevel::Info, || {
+                        format!("0以外の値 0x{:02X} がBootRomに書き込まれました。BootRomを非アクティブにします。", value)
+                    });
+                }
             }
 
             // その他のI/Oレジスタ（未実装）
             _ => {
-                #[cfg(feature = "trace_memory")]
-                println!("未実装I/Oレジスタ書き込み: 0x{:04X} = 0x{:02X}", addr, value);
+                if self.stats_enabled {
+                    self.logger.log(LogLevel::Debug, || {
+                        format!("未実装I/Oレジスタ書き込み: 0x{:04X} = 0x{:02X}", addr, value)
+                    });
+                }
+                self.record_unhandled_io(addr, value);
             }
         }
     }
     
+    /// 現在のエミュレーション状態を保存
+    pub fn save_state(&self) -> PeripheralsState {
+        PeripheralsState {
+            bootrom: self.bootrom.clone(),
+            wram: self.wram.clone(),
+            hram: self.hram.clone(),
+            ppu: self.ppu.clone(),
+            timer: self.timer.clone(),
+            joypad: self.joypad.clone(),
+            dma: self.dma.clone(),
+            cartridge: self.cartridge.clone(),
+            serial: self.serial.clone(),
+            apu: self.apu.clone(),
+            interrupt_flag: self.interrupt_flag,
+            interrupt_enable: self.interrupt_enable,
+        }
+    }
+
+    /// 保存した状態を復元（独自マッパーは現在のものを引き継ぐ）
+    ///
+    /// APUのサンプルバッファとレジスタ書き込みログは復元せず空にする
+    /// (保存時点で未取り出しだった音声やログが再度出てこないように)
+    pub fn load_state(&mut self, state: &PeripheralsState) {
+        let mapper = self.cartridge.as_mut().and_then(|cart| cart.clear_mapper());
+
+        self.bootrom = state.bootrom.clone();
+        self.wram = state.wram.clone();
+        self.hram = state.hram.clone();
+        self.ppu = state.ppu.clone();
+        self.timer = state.timer.clone();
+        self.joypad = state.joypad.clone();
+        self.dma = state.dma.clone();
+        self.cartridge = state.cartridge.clone();
+        self.serial = state.serial.clone();
+        self.apu = state.apu.clone();
+        self.apu.clear_output_buffers();
+        self.interrupt_flag = state.interrupt_flag;
+        self.interrupt_enable = state.interrupt_enable;
+
+        if let (Some(cart), Some(mapper)) = (self.cartridge.as_mut(), mapper) {
+            cart.set_mapper(mapper);
+        }
+    }
+
     /// 16bitデータを読み取る（リトルエンディアン）
+    ///
+    /// バス上は2回のバイト読み取り（2 Mサイクル分）なのでread_countは2進む。
+    /// 論理的な16bitアクセスとしてはread16_countに1回だけ記録する
     pub fn read16(&mut self, addr: u16) -> u16 {
+        if self.stats_enabled {
+            self.read16_count += 1;
+        }
         let low = self.read(addr) as u16;
         let high = self.read(addr.wrapping_add(1)) as u16;
         (high << 8) | low
     }
     
     /// 16bitデータを書き込む（リトルエンディアン）
+    ///
+    /// read16と同様、write_countは2、write16_countは1進む
     pub fn write16(&mut self, addr: u16, value: u16) {
+        if self.stats_enabled {
+            self.write16_count += 1;
+        }
         self.write(addr, value as u8);           // 下位バイト
         self.write(addr.wrapping_add(1), (value >> 8) as u8);  // 上位バイト
     }
@@ -432,6 +767,8 @@ impl Peripherals {
         MemoryStats {
             read_count: self.read_count,
             write_count: self.write_count,
+            read16_count: self.read16_count,
+            write16_count: self.write16_count,
             bootrom_active: self.bootrom.is_active(),
             wram_used_bytes: wram_used,
             wram_total_bytes: wram_total,
@@ -452,36 +789,53 @@ impl Peripherals {
         while addr <= end_addr {
             result.push_str(&format!("0x{:04X}: ", addr));
             
+            // 行内の値を1回だけ読み出す（範囲外はNone）
+            let values: Vec<Option<u8>> = (0..16)
+                .map(|i| addr.checked_add(i).filter(|&a| a <= end_addr))
+                .map(|a| a.map(|a| self.read(a)))
+                .collect();
+            
             // 16進数表示
-            for i in 0..16 {
-                let current_addr = addr + i;
-                if current_addr <= end_addr {
-                    let value = self.read(current_addr);
-                    result.push_str(&format!("{:02X} ", value));
-                } else {
-                    result.push_str("   ");
+            for value in &values {
+                match value {
+                    Some(value) => result.push_str(&format!("{:02X} ", value)),
+                    None => result.push_str("   "),
                 }
             }
             
             result.push_str(" | ");
             
             // ASCII表示
-            for i in 0..16 {
-                let current_addr = addr + i;
-                if current_addr <= end_addr {
-                    let value = self.read(current_addr);
-                    if value >= 32 && value <= 126 {
-                        result.push(value as char);
-                    } else {
-                        result.push('.');
-                    }
-                } else {
-                    result.push(' ');
+            for value in &values {
+                match value {
+                    Some(value) if (32..=126).contains(value) => result.push(*value as char),
+                    Some(_) => result.push('.'),
+                    None => result.push(' '),
                 }
             }
             
             result.push_str(&format!(" [{}]\n", get_region_name(addr)));
-            addr += 16;
+            
+            // I/Oレジスタ領域では既知のレジスタ名と値を併記
+            if (IO_REGISTERS_START..=IO_REGISTERS_END).contains(&addr) {
+                let names: Vec<String> = values
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, value)| {
+                        let current_addr = addr + i as u16;
+                        let name = get_io_register_name(current_addr)?;
+                        Some(format!("0x{:04X} {}={:02X}", current_addr, name, (*value)?))
+                    })
+                    .collect();
+                if !names.is_empty() {
+                    result.push_str(&format!("        {}\n", names.join(", ")));
+                }
+            }
+            
+            match addr.checked_add(16) {
+                Some(next) => addr = next,
+                None => break,
+            }
         }
         
         result
@@ -491,6 +845,8 @@ impl Peripherals {
     pub fn reset_stats(&mut self) {
         self.read_count = 0;
         self.write_count = 0;
+        self.read16_count = 0;
+        self.write16_count = 0;
     }
     
     /// システム全体をリセット
@@ -498,14 +854,21 @@ impl Peripherals {
         self.wram.clear_all();
         self.hram.clear();
         self.reset_stats();
+        self.clear_unhandled_io_report();
         // BootROMは再有効化しない（実際のハードウェアでは不可能）
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct MemoryStats {
+    /// バイト単位の読み取り回数（16bitアクセスは2回として数える）
     pub read_count: u64,
+    /// バイト単位の書き込み回数（16bitアクセスは2回として数える）
     pub write_count: u64,
+    /// read16による16bit読み取りの回数（read_countにも2回分含まれる）
+    pub read16_count: u64,
+    /// write16による16bit書き込みの回数（write_countにも2回分含まれる）
+    pub write16_count: u64,
     pub bootrom_active: bool,
     pub wram_used_bytes: usize,
     pub wram_total_bytes: usize,
@@ -515,17 +878,19 @@ pub struct MemoryStats {
     pub hram_usage_percent: f32,
 }
 
-impl std::fmt::Display for MemoryStats {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for MemoryStats {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, 
             "Memory Statistics:\n\
              - Read operations: {}\n\
              - Write operations: {}\n\
+             - 16-bit reads/writes: {}/{}\n\
              - BootROM active: {}\n\
              - WRAM usage: {}/{} bytes ({:.1}%)\n\
              - HRAM usage: {}/{} bytes ({:.1}%)",
             self.read_count,
             self.write_count,
+            self.read16_count, self.write16_count,
             self.bootrom_active,
             self.wram_used_bytes, self.wram_total_bytes, self.wram_usage_percent,
             self.hram_used_bytes, self.hram_total_bytes, self.hram_usage_percent
@@ -536,6 +901,17 @@ impl std::fmt::Display for MemoryStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logger::LogRecord;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// ログシンクを設定し、記録されたメッセージを溜める共有バッファを返す
+    fn capture_logs(peripherals: &mut Peripherals) -> Rc<RefCell<Vec<LogRecord>>> {
+        let records = Rc::new(RefCell::new(Vec::new()));
+        let captured = Rc::clone(&records);
+        peripherals.set_log_sink(Box::new(move |record| captured.borrow_mut().push(record.clone())));
+        records
+    }
     
     #[test]
     fn test_peripherals_bootrom() {
@@ -555,6 +931,32 @@ mod tests {
         assert_eq!(value, 0xFF);  // カートリッジROM未実装なので0xFF
     }
     
+    #[test]
+    fn test_peripherals_bootrom_cartridge_overlap() {
+        use crate::testing::{build_test_rom, TestRomOptions};
+
+        let mut rom = build_test_rom(TestRomOptions::default());
+        rom[0x0000] = 0x31;
+        rom[0x00FF] = 0x77;
+        rom[0x7FFF] = 0x99;
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        peripherals.load_cartridge(Cartridge::new(rom).unwrap());
+
+        // BootROM有効中: 0x0000-0x00FFはBootROM、0x0100以降はカートリッジ
+        assert_eq!(peripherals.read(0x0000), 0x00);
+        assert_eq!(peripherals.read(0x00FF), 0x00);
+        let entry: Vec<u8> = (0x0100..=0x0103).map(|addr| peripherals.read(addr)).collect();
+        assert_eq!(entry, [0x00, 0xC3, 0x50, 0x01]);
+        assert_eq!(peripherals.read(0x7FFF), 0x99);
+
+        // 0xFF50書き込み後: 0x0000-0x7FFF全体がカートリッジ
+        peripherals.write(0xFF50, 0x01);
+        assert_eq!(peripherals.read(0x0000), 0x31);
+        assert_eq!(peripherals.read(0x00FF), 0x77);
+        assert_eq!(peripherals.read(0x0101), 0xC3);
+        assert_eq!(peripherals.read(0x7FFF), 0x99);
+    }
+
     #[test]
     fn test_peripherals_wram() {
         let mut peripherals = Peripherals::new_with_dummy_bootrom();
@@ -571,6 +973,29 @@ mod tests {
         assert_eq!(peripherals.read(0xC000), 0x99);  // WRAMから読み取り
     }
     
+    #[test]
+    fn test_peripherals_wram_echo_boundaries() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+
+        // エコー領域の終端 0xFDFF は WRAM 0xDDFF と同じバイト
+        peripherals.write(0xFDFF, 0x5A);
+        assert_eq!(peripherals.read(0xDDFF), 0x5A);
+        peripherals.write(0xDDFF, 0xA5);
+        assert_eq!(peripherals.read(0xFDFF), 0xA5);
+
+        // 途中のアドレスも0x2000離れた位置と対応する
+        peripherals.write(0xE123, 0x77);
+        assert_eq!(peripherals.read(0xC123), 0x77);
+
+        // WRAM 0xDE00-0xDFFF にはエコーがない（0xFE00以降はOAM・未使用領域）
+        peripherals.write(0xFE00, 0x00); // OAM
+        for addr in 0xDE00..=0xDFFFu16 {
+            peripherals.write(addr, 0xC3);
+        }
+        assert_eq!(peripherals.read(0xFE00), 0x00);
+        assert_eq!(peripherals.read(0xFDFF), 0xA5);
+    }
+
     #[test]
     fn test_peripherals_hram() {
         let mut peripherals = Peripherals::new_with_dummy_bootrom();
@@ -597,6 +1022,124 @@ mod tests {
         assert_eq!(peripherals.read16(0xC000), 0x1234);
     }
 
+    #[test]
+    fn test_peripherals_16bit_access_stats() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        peripherals.write(0xC000, 0xCD);
+        peripherals.write(0xC001, 0xAB);
+        peripherals.reset_stats();
+
+        // バス上は2バイト読み取り、論理的には1回の16bitアクセス
+        assert_eq!(peripherals.read16(0xC000), 0xABCD);
+        let stats = peripherals.get_stats();
+        assert_eq!(stats.read_count, 2);
+        assert_eq!(stats.read16_count, 1);
+
+        peripherals.write16(0xC002, 0x1234);
+        let stats = peripherals.get_stats();
+        assert_eq!(stats.write_count, 2);
+        assert_eq!(stats.write16_count, 1);
+        assert_eq!(peripherals.read(0xC002), 0x34);
+        assert_eq!(peripherals.read(0xC003), 0x12);
+
+        peripherals.reset_stats();
+        assert_eq!(peripherals.get_stats().read16_count, 0);
+        assert_eq!(peripherals.get_stats().write16_count, 0);
+    }
+
+    #[test]
+    fn test_peripherals_new_with_pattern() {
+        let pattern = MemPattern::Lcg(42);
+        let mut a = Peripherals::new_with_pattern(BootRom::new_dummy(), pattern);
+        let mut b = Peripherals::new_with_pattern(BootRom::new_dummy(), pattern);
+        a.ppu.registers.lcdc = 0x00; // LCD無効でVRAM/OAMを自由に読む
+        b.ppu.registers.lcdc = 0x00;
+
+        for addr in [0x8000u16, 0x9FFF, 0xC000, 0xDFFF, 0xFE00, 0xFF80] {
+            assert_eq!(a.read(addr), b.read(addr), "addr={:04X}", addr);
+        }
+        assert!((0xC000..0xC100).any(|addr| a.read(addr) != 0));
+        assert!((0xFF80..0xFFFF).any(|addr| a.read(addr) != 0));
+        assert!((0x8000..0x8100).any(|addr| a.read(addr) != 0));
+
+        // 領域ごとに異なる乱数列で始まる
+        let starts: Vec<Vec<u8>> = [0x8000u16, 0xC000, 0xFE00, 0xFF80].iter()
+            .map(|&base| (base..base + 16).map(|addr| a.read(addr)).collect())
+            .collect();
+        for i in 0..starts.len() {
+            for j in i + 1..starts.len() {
+                assert_ne!(starts[i], starts[j]);
+            }
+        }
+
+        let mut ones = Peripherals::new_with_pattern(BootRom::new_dummy(), MemPattern::Ones);
+        assert_eq!(ones.read(0xC123), 0xFF);
+        assert_eq!(ones.read(0xFF90), 0xFF);
+    }
+
+    #[test]
+    fn test_peripherals_stats_disabled() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        let records = capture_logs(&mut peripherals);
+        peripherals.set_audit_mode(true);
+        assert!(peripherals.is_stats_enabled());
+
+        peripherals.set_stats_enabled(false);
+        for i in 0..10_000u16 {
+            peripherals.write(0xC000 + (i & 0xFF), i as u8);
+            peripherals.read(0xC000 + (i & 0xFF));
+        }
+        peripherals.write16(0xC000, 0x1234);
+        peripherals.read16(0xC000);
+        // 未実装I/Oへのアクセスはログされないが、監査モードの記録は続く
+        peripherals.write(0xFF7F, 0x00);
+        peripherals.read(0xFF7F);
+
+        let stats = peripherals.get_stats();
+        assert_eq!(stats.read_count, 0);
+        assert_eq!(stats.write_count, 0);
+        assert_eq!(stats.read16_count, 0);
+        assert_eq!(stats.write16_count, 0);
+        assert_eq!(peripherals.unhandled_io_report(), vec![(0xFF7F, 2)]);
+        assert!(records.borrow().is_empty());
+
+        // 無効中もメモリアクセス自体は正しく行われる
+        assert_eq!(peripherals.read(0xC000), 0x34);
+
+        // 再度有効にすると計数が再開する
+        peripherals.set_stats_enabled(true);
+        peripherals.read(0xC000);
+        assert_eq!(peripherals.get_stats().read_count, 1);
+    }
+
+    // 統計有効/無効でのバスアクセスのスループット比較
+    // cargo test --release bench_stats_overhead -- --ignored --nocapture で実行
+    #[test]
+    #[ignore]
+    fn bench_stats_overhead() {
+        const ACCESSES: u32 = 50_000_000;
+        for enabled in [true, false] {
+            let mut peripherals = Peripherals::new_with_dummy_bootrom();
+            peripherals.set_stats_enabled(enabled);
+            let start = std::time::Instant::now();
+            let mut acc = 0u8;
+            for i in 0..ACCESSES {
+                let addr = 0xC000 + (i & 0x0FFF) as u16;
+                peripherals.write(addr, acc);
+                acc = acc.wrapping_add(peripherals.read(addr));
+            }
+            let elapsed = start.elapsed();
+            println!(
+                "stats={}: {} アクセス / {:.3} 秒 = {:.1} Mアクセス/秒 (acc={})",
+                enabled,
+                ACCESSES * 2,
+                elapsed.as_secs_f64(),
+                (ACCESSES * 2) as f64 / elapsed.as_secs_f64() / 1_000_000.0,
+                acc
+            );
+        }
+    }
+
     #[test]
     fn test_peripherals_vram() {
         let mut peripherals = Peripherals::new_with_dummy_bootrom();
@@ -609,6 +1152,26 @@ mod tests {
         assert_eq!(peripherals.read(0x9FFF), 0x55);
     }
 
+    #[test]
+    fn test_peripherals_vram_tile_round_trip() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        // タイル1 (0x8010-0x801F) に市松模様を書き込む
+        let tile: [u8; 16] = core::array::from_fn(|i| if i % 4 < 2 { 0xAA } else { 0x55 });
+        for (i, &byte) in tile.iter().enumerate() {
+            peripherals.write(0x8010 + i as u16, byte);
+        }
+        for (i, &byte) in tile.iter().enumerate() {
+            assert_eq!(peripherals.read(0x8010 + i as u16), byte);
+            assert_eq!(peripherals.ppu.read_vram(0x8010 + i as u16), byte);
+        }
+
+        // Drawing中の書き込みは破棄される
+        peripherals.ppu.mode = crate::ppu::PpuMode::Drawing;
+        peripherals.write(0x8010, 0x00);
+        peripherals.ppu.mode = crate::ppu::PpuMode::HBlank;
+        assert_eq!(peripherals.read(0x8010), tile[0]);
+    }
+
     #[test]
     fn test_peripherals_oam() {
         let mut peripherals = Peripherals::new_with_dummy_bootrom();
@@ -684,6 +1247,25 @@ mod tests {
         assert_eq!(joyp & 0x01, 0x00); // bit0=0 (Right押下)
     }
 
+    #[test]
+    fn test_peripherals_joypad_unused_bits() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+
+        // 両グループ未選択: 全ボタン離し、上位2bitは常に1
+        peripherals.write(0xFF00, 0x30);
+        assert_eq!(peripherals.read(0xFF00), 0xFF);
+
+        // 同じ値の再書き込みでも選択bitはそのまま読み返せる
+        peripherals.write(0xFF00, 0x30);
+        assert_eq!(peripherals.read(0xFF00) & 0x30, 0x30);
+
+        // 書き込めるのはbit4-5のみ（上位2bitと下位4bitは無視される）
+        peripherals.write(0xFF00, 0x00);
+        assert_eq!(peripherals.read(0xFF00), 0xCF);
+        peripherals.write(0xFF00, 0xD0);
+        assert_eq!(peripherals.read(0xFF00), 0xDF);
+    }
+
     #[test]
     fn test_peripherals_dma() {
         let mut peripherals = Peripherals::new_with_dummy_bootrom();
@@ -710,6 +1292,65 @@ mod tests {
         assert_eq!(peripherals.read(0xFE9F), 159);
     }
 
+    #[test]
+    fn test_peripherals_dma_restricts_to_hram() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        peripherals.write(0xC000, 0x42);
+        peripherals.write(0xFF80, 0x99);
+
+        // DMA開始
+        peripherals.write(0xFF46, 0xC0);
+        peripherals.tick(8);
+
+        // 転送中: WRAMは0xFF、HRAMは通常通り
+        assert_eq!(peripherals.read(0xC000), 0xFF);
+        assert_eq!(peripherals.read(0xFF80), 0x99);
+        peripherals.write(0xFF81, 0x55);
+        assert_eq!(peripherals.read(0xFF81), 0x55);
+
+        // WRAMへの書き込みは無視、IE/IFはアクセス可能
+        peripherals.write(0xC001, 0x77);
+        peripherals.write(0xFFFF, 0x01);
+        assert_eq!(peripherals.read(0xFFFF), 0x01);
+
+        // 転送完了後は通常通り
+        for _ in 0..700 {
+            peripherals.tick(1);
+        }
+        assert!(!peripherals.dma.is_active());
+        assert_eq!(peripherals.read(0xC000), 0x42);
+        assert_eq!(peripherals.read(0xC001), 0x00);
+    }
+
+    #[test]
+    fn test_peripherals_dma_restart_during_transfer() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        for i in 0..160u8 {
+            peripherals.write(0xC000 + i as u16, i);
+            peripherals.write(0xD000 + i as u16, 0xFF - i);
+        }
+
+        // 40バイト転送したところで転送元を変えて再開始
+        peripherals.write(0xFF46, 0xC0);
+        peripherals.tick(160);
+        assert!(peripherals.dma.is_active());
+        peripherals.write(0xFF46, 0xD0);
+        assert_eq!(peripherals.read(0xFF46), 0xD0);
+
+        // 再開始から160 Mサイクルで転送が終わり、OAM全体が新しい転送元で上書きされる
+        for _ in 0..636 {
+            peripherals.tick(1);
+        }
+        assert!(peripherals.dma.is_active());
+        peripherals.tick(4);
+        assert!(!peripherals.dma.is_active());
+
+        peripherals.ppu.mode = crate::ppu::PpuMode::HBlank;
+        assert_eq!(peripherals.read(0xFE00), 0xFF);
+        assert_eq!(peripherals.read(0xFE27), 0xFF - 0x27);
+        assert_eq!(peripherals.read(0xFE9F), 0xFF - 159);
+    }
+
     #[test]
     fn test_peripherals_obp_registers() {
         let mut peripherals = Peripherals::new_with_dummy_bootrom();
@@ -818,4 +1459,151 @@ mod tests {
         assert_eq!(peripherals.read(0xFF30), 0x12);
         assert_eq!(peripherals.read(0xFF3F), 0xAB);
     }
+
+    #[test]
+    fn test_peripherals_audit_mode_unhandled_io() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+
+        // 監査モード無効時は記録しない
+        peripherals.read(0xFF4C);
+        assert!(peripherals.unhandled_io_report().is_empty());
+
+        peripherals.set_audit_mode(true);
+        for _ in 0..3 {
+            assert_eq!(peripherals.read(0xFF4C), 0xFF);
+        }
+        peripherals.write(0xFF4C, 0x12);
+        peripherals.write(0xFF7F, 0x34);
+
+        // 実装済みレジスタは記録されない
+        peripherals.read(0xFF40);
+        peripherals.write(0xFF47, 0xE4);
+
+        assert_eq!(peripherals.unhandled_io_report(), vec![(0xFF4C, 4), (0xFF7F, 1)]);
+        let access = peripherals.unhandled_io_access(0xFF4C).unwrap();
+        assert_eq!(access.last_value, 0x12);
+
+        // 統計リセットでは記録は残り、専用のクリアで消える
+        peripherals.reset_stats();
+        assert_eq!(peripherals.unhandled_io_report(), vec![(0xFF4C, 4), (0xFF7F, 1)]);
+        peripherals.clear_unhandled_io_report();
+        assert!(peripherals.unhandled_io_report().is_empty());
+    }
+
+    #[test]
+    fn test_peripherals_watchpoint() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        peripherals.add_watchpoint(0xC100..=0xC1FF, WatchKind::Read);
+
+        // 範囲外・種別違いのアクセスはヒットしない
+        peripherals.write(0xC100, 0x11);
+        peripherals.read(0xC200);
+        assert_eq!(peripherals.take_watchpoint_hit(), None);
+
+        peripherals.read(0xC180);
+        peripherals.read(0xC181); // 最初のヒットのみ保持
+        let hit = peripherals.take_watchpoint_hit().unwrap();
+        assert_eq!(hit.addr, 0xC180);
+        assert!(!hit.is_write);
+        assert_eq!(peripherals.take_watchpoint_hit(), None);
+
+        peripherals.clear_watchpoints();
+        peripherals.read(0xC180);
+        assert_eq!(peripherals.take_watchpoint_hit(), None);
+    }
+
+    #[test]
+    fn test_peripherals_log_sink() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        let records = capture_logs(&mut peripherals);
+
+        // 未実装I/Oレジスタへのアクセスはログに記録される
+        peripherals.read(0xFF4C);
+        peripherals.write(0xFF50, 0x01);
+
+        let records = records.borrow();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].level, LogLevel::Debug);
+        assert!(records[0].message.contains("0xFF4C"));
+        assert_eq!(records[1].level, LogLevel::Info);
+    }
+
+    #[test]
+    fn test_peripherals_shared_tile_write_log() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        let records = capture_logs(&mut peripherals);
+        peripherals.write(0xFF40, 0x00); // LCD無効（VRAMに書き込めるように）
+
+        // 無効時はログを出さない
+        peripherals.write(0x8800, 0x11);
+        assert!(records.borrow().is_empty());
+
+        peripherals.set_log_shared_tile_writes(true);
+        peripherals.write(0x8000, 0x22); // 符号なし専用
+        peripherals.write(0x9000, 0x33); // 符号付き専用
+        peripherals.write(0x8FF0, 0x44); // 共有領域
+        assert_eq!(peripherals.read(0x8FF0), 0x44);
+
+        let records = records.borrow();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].level, LogLevel::Debug);
+        assert!(records[0].message.contains("0x8FF0"));
+        assert!(records[0].message.contains("符号付きID -1"));
+    }
+
+    #[test]
+    fn test_peripherals_rom_size_mismatch_warning() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        let records = capture_logs(&mut peripherals);
+
+        // サイズが一致するROMは警告なし
+        peripherals.load_cartridge(Cartridge::new_rom_only(vec![0; 0x8000]));
+        assert!(records.borrow().is_empty());
+
+        // ヘッダは64KB、実データは32KB
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0147] = 0x01;
+        rom[0x0148] = 0x01;
+        peripherals.load_cartridge(Cartridge::new(rom).unwrap());
+
+        let records = records.borrow();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].level, LogLevel::Warn);
+        assert!(records[0].message.contains("65536"));
+        assert!(records[0].message.contains("32768"));
+    }
+
+    #[test]
+    fn test_peripherals_vblank_interrupt_timing() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        peripherals.write(0xFF41, 0x10); // STATモード1割り込み有効
+
+        // ライン0〜143の直前 (144 × 456 - 1 サイクル) まではVBlankに入らない
+        for _ in 0..(144 * 456 - 1) {
+            assert!(!peripherals.tick(1));
+        }
+        assert_eq!(peripherals.read(0xFF44), 143);
+        assert_eq!(peripherals.interrupt_flag & 0x03, 0x00);
+
+        // ライン144に入ったサイクルでIF bit0とSTAT割り込み(bit1)が同時にセット
+        assert!(peripherals.tick(1));
+        assert_eq!(peripherals.read(0xFF44), 144);
+        assert_eq!(peripherals.read(0xFF41) & 0x03, 0x01);
+        assert_eq!(peripherals.interrupt_flag & 0x03, 0x03);
+    }
+
+    #[test]
+    fn test_peripherals_dump_memory_io_names() {
+        let mut peripherals = Peripherals::new_with_dummy_bootrom();
+        peripherals.write(0xFF40, 0x91); // LCDC
+
+        let dump = peripherals.dump_memory(0xFF40, 0xFF4F);
+        assert!(dump.contains("0xFF40: 91 "));
+        assert!(dump.contains("0xFF40 LCDC=91"));
+        assert!(dump.contains("0xFF47 BGP="));
+
+        // I/O領域以外ではレジスタ名を併記しない
+        let dump = peripherals.dump_memory(0xC000, 0xC00F);
+        assert_eq!(dump.lines().count(), 2);
+    }
 }
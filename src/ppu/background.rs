@@ -185,6 +185,17 @@ impl BackgroundRenderer {
     pub fn clear_cache(&mut self) {
         self.tile_renderer.clear_cache();
     }
+
+    // タイルキャッシュの有効/無効を切り替え（キャッシュ有無の出力比較用）
+    pub fn set_caching(&mut self, enabled: bool) {
+        self.tile_renderer.set_caching(enabled);
+    }
+}
+
+impl Default for BackgroundRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // 背景スクロール情報
@@ -271,4 +282,37 @@ mod tests {
         assert_eq!(bg_x, 0);  // 1 + 255 = 256 -> 0 (u8のラップアラウンド)
         assert_eq!(bg_y, 0);  // 1 + 255 = 256 -> 0 (u8のラップアラウンド)
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_caching_vs_uncached_after_vram_write() {
+        let mut vram = Vram::new();
+        let mut registers = PpuRegisters::new();
+        registers.lcdc = 0x91; // LCD有効、BG有効、タイルデータ0x8000
+        registers.bgp = 0xE4;
+
+        // タイル0の先頭行を色1で埋める（タイルマップは全てタイル0）
+        vram.write(0x0000, 0xFF);
+
+        let mut cached = BackgroundRenderer::new();
+        let mut uncached = BackgroundRenderer::new();
+        uncached.set_caching(false);
+
+        let before = cached.render_scanline(&vram, &registers, 0);
+        assert_eq!(uncached.render_scanline(&vram, &registers, 0), before);
+
+        // VRAMを書き換え: 先頭行を色3に
+        vram.write(0x0001, 0xFF);
+
+        // キャッシュなしは変更を反映する
+        let fresh = uncached.render_scanline(&vram, &registers, 0);
+        let (r, g, b) = ColorConverter::dmg_to_rgb888(3);
+        assert_eq!(fresh[0..3], [r, g, b]);
+
+        // キャッシュありもVRAMの世代番号の変化で無効化され、同じ結果になる
+        assert_ne!(fresh, before);
+        assert_eq!(cached.render_scanline(&vram, &registers, 0), fresh);
+
+        // 書き込みがなければキャッシュを使い続けても同じ結果
+        assert_eq!(cached.render_scanline(&vram, &registers, 0), fresh);
+    }
+}
@@ -5,7 +5,10 @@ pub mod tiles;
 pub mod background;
 pub mod sprites;
 
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use crate::memory::MemPattern;
 use crate::memory_map::{dmg, io_registers};
+use registers::stat;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PpuMode {
@@ -15,6 +18,23 @@ pub enum PpuMode {
     Drawing = 3,     // Mode 3: Drawing
 }
 
+// 画面ピクセルの描画元（デバッグ用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelSource {
+    None,  // 未記録、またはBG無効
+    Background { tile_index: u8, map_address: u16 },  // map_address: タイルマップの絶対アドレス
+    Window { tile_index: u8, map_address: u16 },
+    Sprite { tile_index: u8, oam_index: u8 },
+}
+
+// OAMバグの破損パターン
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OamBugPattern {
+    Write,  // 書き込み / 16bit INC・DEC
+    Read,   // 読み取り
+}
+
+#[derive(Clone)]
 pub struct Ppu {
     pub registers: registers::PpuRegisters,
     pub vram: vram::Vram,
@@ -34,9 +54,49 @@ pub struct Ppu {
     // BG色ID配列（スプライト優先度判定用）
     bg_color_ids: [u8; 160],
 
-    // フラグ
-    pub vblank_interrupt: bool,
-    pub stat_interrupt: bool,
+    // 画面全体のパレット適用後の色番号（0=最明〜3=最暗、スプライト込み）
+    color_ids: [u8; 160 * 144],
+
+    // モード3開始時にラッチしたBGP（スキャンライン単位の描画はこの値を使う）
+    // 1ライン内の途中でのBGP変更（そのピクセル以降のみに反映）はピクセルFIFO描画でないと再現できない
+    line_bgp: Option<u8>,
+
+    // 割り込み要求フラグ（take_*で読み出すと同時にクリア）
+    vblank_interrupt: bool,
+    stat_interrupt: bool,
+
+    // 完了したフレーム数（ライン153→0の折り返しごとに加算）
+    frame_count: u64,
+    // フレーム完了のラッチ（take_frame_readyで読み出すと同時にクリア）
+    frame_ready: bool,
+
+    // OAMバグエミュレーション（デフォルト無効）
+    pub oam_bug: bool,
+
+    // WX=0のウィンドウずれ（SCX下位3bit分左にずれる）の再現（デフォルト無効）
+    pub wx0_glitch: bool,
+
+    // スキャンライン描画を省略する（早送りのフレームスキップ用、タイミング・割り込みは通常通り）
+    pub skip_rendering: bool,
+
+    // モード制限で破棄されたVRAM/OAM書き込みをアドレスごとに数える（デバッグ用、デフォルト無効）
+    pub track_dropped_writes: bool,
+    dropped_writes: BTreeMap<u16, u64>,
+
+    // ピクセル描画元の記録（デバッグ用、無効時は空）
+    pixel_sources: Vec<PixelSource>,
+
+    // スキャンラインごとのモード記録（デバッグ用、無効時は空）
+    mode_timeline: Vec<PpuMode>,
+
+    // LCD無効により停止中か
+    lcd_stopped: bool,
+
+    // LCD有効化直後のライン0（OAMスキャン期間はSTATにモード0を示す）
+    lcd_enable_line0: bool,
+
+    // 現在のフレームのスプライト統計（ライン0の描画開始時にリセット）
+    sprite_stats: sprites::SpriteStats,
 }
 
 impl Ppu {
@@ -54,24 +114,70 @@ impl Ppu {
 
             framebuffer: [0; 160 * 144 * 3],
             bg_color_ids: [0; 160],
+            color_ids: [0; 160 * 144],
+
+            line_bgp: None,
 
             vblank_interrupt: false,
             stat_interrupt: false,
+
+            frame_count: 0,
+            frame_ready: false,
+
+            oam_bug: false,
+
+            wx0_glitch: false,
+
+            skip_rendering: false,
+
+            track_dropped_writes: false,
+            dropped_writes: BTreeMap::new(),
+
+            pixel_sources: Vec::new(),
+
+            mode_timeline: Vec::new(),
+
+            lcd_stopped: false,
+
+            lcd_enable_line0: false,
+
+            sprite_stats: sprites::SpriteStats::default(),
         }
     }
+
+    /// VRAM/OAMを指定パターンで初期化したPPUを作成
+    pub fn new_with_pattern(pattern: MemPattern) -> Self {
+        let mut ppu = Self::new();
+        ppu.vram = vram::Vram::new_with_pattern(pattern.for_region(dmg::VRAM_START));
+        pattern.for_region(dmg::OAM_START).fill(&mut ppu.oam);
+        ppu
+    }
     
     // PPUを1サイクル進める
     pub fn step(&mut self) -> bool {
+        // LCD無効時は停止（LY=0、モード0でVRAM/OAMにアクセス可能）
+        if !self.registers.is_lcd_enabled() {
+            if !self.lcd_stopped {
+                self.stop_lcd();
+            }
+            return false;
+        }
+
+        if self.lcd_stopped {
+            self.restart_lcd();
+        }
+
         self.cycles += 1;
-        
-        // LYレジスタを更新
-        self.registers.ly = self.scanline;
+        let mut vblank = false;
         
         match self.mode {
             PpuMode::OamScan => {
                 if self.cycles >= 80 {
                     self.mode = PpuMode::Drawing;
                     self.cycles = 0;
+                    self.lcd_enable_line0 = false;
+                    // 描画開始時点のBGPをラッチ（モード3中の書き込みは次のラインから反映）
+                    self.line_bgp = Some(self.registers.bgp);
                 }
             },
             PpuMode::Drawing => {
@@ -80,7 +186,7 @@ impl Ppu {
                     self.cycles = 0;
                     
                     // スキャンライン描画
-                    if self.registers.is_lcd_enabled() {
+                    if self.registers.is_lcd_enabled() && !self.skip_rendering {
                         self.draw_scanline();
                     }
                 }
@@ -91,10 +197,14 @@ impl Ppu {
                     self.cycles = 0;
                     
                     if self.scanline >= 144 {
-                        // VBlank開始
+                        // VBlank開始: ライン144に入ったサイクルでVBlank割り込みを要求
                         self.mode = PpuMode::VBlank;
                         self.vblank_interrupt = true;
-                        return true;  // VBlank割り込み発生
+                        // STATモード1割り込みも同時に発生
+                        if self.registers.is_vblank_interrupt_enabled() {
+                            self.stat_interrupt = true;
+                        }
+                        vblank = true;
                     } else {
                         self.mode = PpuMode::OamScan;
                     }
@@ -107,20 +217,180 @@ impl Ppu {
                     
                     if self.scanline >= 154 {
                         // フレーム完了、新しいフレーム開始
+                        // 前フレームで取り出されなかった割り込み要求は破棄（二重処理防止）
+                        self.vblank_interrupt = false;
+                        self.stat_interrupt = false;
                         self.scanline = 0;
                         self.window_line_counter = 0;
                         self.mode = PpuMode::OamScan;
+                        self.frame_count += 1;
+                        self.frame_ready = true;
                     }
                 }
             },
         }
         
-        // STATレジスタを更新
-        self.registers.stat = (self.registers.stat & 0xFC) | (self.mode as u8);
+        // LY/STATレジスタを更新（ライン・モード遷移と同じサイクルで反映）
+        self.registers.ly = self.ly();
+        self.registers.stat = (self.registers.stat & !stat::MODE_MASK) | self.stat_mode();
+        
+        // モードタイムライン記録（有効時のみ）
+        if !self.mode_timeline.is_empty() && self.line_dot() == timing::MODE_TIMELINE_SAMPLE_DOT {
+            self.mode_timeline[self.scanline as usize] = self.mode;
+        }
         
-        false
+        vblank
+    }
+    
+    /// LCDCレジスタを設定（bit7の変化によるLCD停止/再開を即座に反映）
+    pub fn set_lcdc(&mut self, value: u8) {
+        let was_enabled = self.registers.is_lcd_enabled();
+        self.registers.lcdc = value;
+        let enabled = self.registers.is_lcd_enabled();
+
+        if was_enabled && !enabled {
+            self.stop_lcd();
+        } else if enabled && self.lcd_stopped {
+            self.restart_lcd();
+        }
+    }
+
+    /// LCDCレジスタの値を取得
+    pub fn get_lcdc(&self) -> u8 {
+        self.registers.lcdc
+    }
+
+    /// STATの下位2bitに示すモード
+    ///
+    /// LCD有効化直後のライン0はOAMスキャンを行わず、その期間はモード0を示す
+    pub fn stat_mode(&self) -> u8 {
+        if self.lcd_enable_line0 && self.mode == PpuMode::OamScan {
+            PpuMode::HBlank as u8
+        } else {
+            self.mode as u8
+        }
+    }
+
+    // LCD停止: LY=0、モード0で停止する
+    fn stop_lcd(&mut self) {
+        self.lcd_stopped = true;
+        self.lcd_enable_line0 = false;
+        self.line_bgp = None;
+        self.mode = PpuMode::HBlank;
+        self.scanline = 0;
+        self.cycles = 0;
+        self.window_line_counter = 0;
+        self.registers.ly = 0;
+        self.registers.stat &= !stat::MODE_MASK;
+    }
+
+    // LCD再開: ライン0から再開する。最初のラインはOAMスキャン期間が短く、STATはモード0を示す
+    fn restart_lcd(&mut self) {
+        self.lcd_stopped = false;
+        self.lcd_enable_line0 = true;
+        self.mode = PpuMode::OamScan;
+        self.scanline = 0;
+        self.cycles = timing::LCD_ENABLE_LINE0_SKIP_CYCLES;
+        self.registers.ly = 0;
+        self.registers.stat &= !stat::MODE_MASK;
+    }
+
+    // 現在のライン内の経過ドット（0-455）
+    fn line_dot(&self) -> u32 {
+        match self.mode {
+            PpuMode::OamScan | PpuMode::VBlank => self.cycles,
+            PpuMode::Drawing => timing::CYCLES_OAM_SCAN + self.cycles,
+            PpuMode::HBlank => timing::CYCLES_OAM_SCAN + timing::CYCLES_DRAWING + self.cycles,
+        }
     }
     
+    /// CPUから見えるLYの値
+    ///
+    /// ライン153は開始直後の数サイクルだけLY=153を示し、残りの期間は既に0を返す
+    /// （内部のスキャンラインは154到達までライン153のまま）
+    pub fn ly(&self) -> u8 {
+        if self.scanline == 153 && self.cycles >= timing::LINE_153_LY_ZERO_CYCLES {
+            0
+        } else {
+            self.scanline
+        }
+    }
+
+    /// 現在のフレームのスプライト統計
+    ///
+    /// ライン0の描画開始時にリセットされるため、VBlank中は直前のフレーム全体の累計になる
+    pub fn sprite_stats(&self) -> sprites::SpriteStats {
+        self.sprite_stats
+    }
+
+    /// ピクセル描画元の記録を有効/無効にする（描画のオーバーヘッドを避けるためデフォルト無効）
+    pub fn set_pixel_source_recording(&mut self, enabled: bool) {
+        self.pixel_sources = if enabled {
+            vec![PixelSource::None; 160 * 144]
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// スキャンラインごとのモード記録を有効/無効にする（デフォルト無効）
+    pub fn set_mode_timeline_recording(&mut self, enabled: bool) {
+        self.mode_timeline = if enabled {
+            vec![PpuMode::HBlank; timing::SCANLINES_TOTAL as usize]
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// 各スキャンラインの代表点（ライン内ドットMODE_TIMELINE_SAMPLE_DOT）でのモード
+    ///
+    /// 各ラインは最後に通過した時点の値で上書きされるため、直近1フレーム分のタイミングを表す。
+    /// 記録無効時、または未通過のラインはHBlank（LCD停止中と同じ値）
+    pub fn mode_timeline(&self) -> [PpuMode; timing::SCANLINES_TOTAL as usize] {
+        core::array::from_fn(|line| self.mode_timeline.get(line).copied().unwrap_or(PpuMode::HBlank))
+    }
+
+    /// VRAMの全384タイルを横16×縦24タイルに並べた色ID(0-3)の画像を出力（タイル編集ツール用）
+    ///
+    /// 戻り値は (1ピクセル1バイトのバッファ, 幅128, 高さ192)。タイル番号nは(n%16, n/16)の位置に置かれ、
+    /// パレットは適用しない
+    pub fn export_tile_atlas(&self) -> (Vec<u8>, usize, usize) {
+        const ATLAS_COLUMNS: usize = 16;
+        let tile_count = vram::TILE_COUNT as usize;
+        let width = ATLAS_COLUMNS * 8;
+        let height = tile_count / ATLAS_COLUMNS * 8;
+        let mut buffer = vec![0u8; width * height];
+
+        for index in 0..tile_count {
+            let tile = self.vram.tile(index as u16);
+            let origin_x = (index % ATLAS_COLUMNS) * 8;
+            let origin_y = (index / ATLAS_COLUMNS) * 8;
+            for (y, row) in tile.pixels.iter().enumerate() {
+                let start = (origin_y + y) * width + origin_x;
+                buffer[start..start + 8].copy_from_slice(row);
+            }
+        }
+
+        (buffer, width, height)
+    }
+
+    /// 画面ピクセル(x, y)がどのBG/ウィンドウ/スプライトから描画されたかを取得
+    pub fn debug_pixel_source(&self, x: u8, y: u8) -> PixelSource {
+        if x >= 160 || y >= 144 {
+            return PixelSource::None;
+        }
+        self.pixel_sources
+            .get(y as usize * 160 + x as usize)
+            .copied()
+            .unwrap_or(PixelSource::None)
+    }
+
+    // ピクセル描画元を記録（記録無効時は何もしない）
+    fn set_pixel_source(&mut self, x: usize, y: usize, source: PixelSource) {
+        if let Some(slot) = self.pixel_sources.get_mut(y * 160 + x) {
+            *slot = source;
+        }
+    }
+
     // スキャンライン描画（BG + ウィンドウ + スプライト）
     fn draw_scanline(&mut self) {
         let y = self.scanline as usize;
@@ -131,8 +401,22 @@ impl Ppu {
         // BG色ID配列をクリア
         self.bg_color_ids = [0; 160];
 
+        // このラインのBGP（stepを経由せず直接描画した場合は現在値）
+        let bgp = self.line_bgp.take().unwrap_or(self.registers.bgp);
+
+        // 新しいフレームの開始でスプライト統計をリセット
+        if y == 0 {
+            self.sprite_stats = sprites::SpriteStats::default();
+        }
+
+        // 描画元の記録をクリア
+        if !self.pixel_sources.is_empty() {
+            self.pixel_sources[y * 160..(y + 1) * 160].fill(PixelSource::None);
+        }
+
         if !self.registers.is_bg_enabled() {
             // BG無効時は白で塗りつぶし
+            self.color_ids[y * 160..(y + 1) * 160].fill(0);
             for x in 0..160 {
                 let pixel_index = (y * 160 + x) * 3;
                 self.framebuffer[pixel_index] = 0x9B;     // R (最明色)
@@ -141,27 +425,37 @@ impl Ppu {
             }
         } else {
             // 背景描画
-            self.draw_bg_scanline(y);
+            self.draw_bg_scanline(y, bgp);
 
             // ウィンドウ描画
-            self.draw_window_scanline(y);
+            self.draw_window_scanline(y, bgp);
         }
 
         // スプライト描画
         let start = y * 160 * 3;
         let end = start + 160 * 3;
-        sprites::SpriteRenderer::render_scanline(
+        let sources = if self.pixel_sources.is_empty() {
+            None
+        } else {
+            Some(&mut self.pixel_sources[y * 160..(y + 1) * 160])
+        };
+        let line_stats = sprites::SpriteRenderer::render_scanline_with_sources(
             &self.oam,
             &self.vram,
             &self.registers,
             self.scanline,
             &self.bg_color_ids,
-            &mut self.framebuffer[start..end],
+            sprites::SpriteLineTargets {
+                rgb: &mut self.framebuffer[start..end],
+                sources,
+                shades: Some(&mut self.color_ids[y * 160..(y + 1) * 160]),
+            },
         );
+        self.sprite_stats.accumulate(&line_stats);
     }
 
     // 背景スキャンライン描画
-    fn draw_bg_scanline(&mut self, y: usize) {
+    fn draw_bg_scanline(&mut self, y: usize, bgp: u8) {
         let bg_y = (y as u8).wrapping_add(self.registers.scy);
         let tile_y = bg_y / 8;
         let pixel_y = bg_y % 8;
@@ -194,8 +488,13 @@ impl Ppu {
 
             // BG色IDを保存（スプライト優先度判定用）
             self.bg_color_ids[x] = color_id;
+            self.set_pixel_source(x, y, PixelSource::Background {
+                tile_index: tile_id,
+                map_address: dmg::VRAM_START + tile_map_addr,
+            });
 
-            let palette_color = self.registers.get_bg_palette_color(color_id);
+            let palette_color = registers::PpuRegisters::apply_palette(bgp, color_id);
+            self.color_ids[y * 160 + x] = palette_color;
             let (r, g, b) = tiles::ColorConverter::dmg_to_rgb888(palette_color);
 
             let pixel_index = (y * 160 + x) * 3;
@@ -206,7 +505,7 @@ impl Ppu {
     }
 
     // ウィンドウスキャンライン描画
-    fn draw_window_scanline(&mut self, y: usize) {
+    fn draw_window_scanline(&mut self, y: usize, bgp: u8) {
         if !self.registers.is_window_enabled() {
             return;
         }
@@ -224,7 +523,13 @@ impl Ppu {
             return;
         }
 
-        let window_x_start = if wx < 7 { 0 } else { (wx - 7) as usize };
+        // WX<7ではウィンドウ左端が画面外に出るため、画面X=0にはウィンドウの (7 - WX) 列目が来る
+        let window_x_start = wx.saturating_sub(7) as usize;
+        let mut left_clip = 7u8.saturating_sub(wx);
+        if wx == 0 && self.wx0_glitch {
+            // WX=0ではフェッチャがSCXの端数分を追加で捨てるため、さらに左にずれる
+            left_clip += self.registers.scx & 0x07;
+        }
 
         let tilemap_base = if self.registers.is_window_tilemap_high() {
             0x1C00
@@ -240,7 +545,7 @@ impl Ppu {
         let mut window_drawn = false;
 
         for x in window_x_start..160 {
-            let window_x = (x - window_x_start) as u8;
+            let window_x = (x - window_x_start) as u8 + left_clip;
             let tile_x = window_x / 8;
             let pixel_x_in_tile = window_x % 8;
 
@@ -259,8 +564,13 @@ impl Ppu {
 
             // ウィンドウ部分のBG色IDを更新
             self.bg_color_ids[x] = color_id;
+            self.set_pixel_source(x, y, PixelSource::Window {
+                tile_index: tile_id,
+                map_address: dmg::VRAM_START + tile_map_addr,
+            });
 
-            let palette_color = self.registers.get_bg_palette_color(color_id);
+            let palette_color = registers::PpuRegisters::apply_palette(bgp, color_id);
+            self.color_ids[y * 160 + x] = palette_color;
             let (r, g, b) = tiles::ColorConverter::dmg_to_rgb888(palette_color);
 
             let pixel_index = (y * 160 + x) * 3;
@@ -290,6 +600,53 @@ impl Ppu {
         }
     }
     
+    /// 画面全体のパレット適用後の色番号（0=最明〜3=最暗、160×144）
+    ///
+    /// framebufferと同じ内容をRGBを経由せずに表す。SimpleDisplay::present_color_idsにそのまま渡せる
+    pub fn color_ids(&self) -> &[u8; 160 * 144] {
+        &self.color_ids
+    }
+
+    /// 完了したフレーム数（ライン153からライン0へ折り返すごとに1増える）
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// 前回の呼び出し以降にフレームが完了していればtrue（読み出すとクリア）
+    ///
+    /// stepの戻り値を見ずにまとめてtickする呼び出し側がフレーム完了を検出する用途
+    pub fn take_frame_ready(&mut self) -> bool {
+        core::mem::take(&mut self.frame_ready)
+    }
+
+    /// 次のモード遷移までのサイクル数（LCD無効時はNone）
+    ///
+    /// PPUの割り込み要求はモード遷移のサイクルでのみ発生するため、HALT中の早送りの上限に使う
+    pub fn cycles_until_mode_change(&self) -> Option<u32> {
+        if !self.registers.is_lcd_enabled() {
+            return None;
+        }
+        let length = match self.mode {
+            PpuMode::OamScan => timing::CYCLES_OAM_SCAN,
+            PpuMode::Drawing => timing::CYCLES_DRAWING,
+            PpuMode::HBlank => timing::CYCLES_HBLANK,
+            PpuMode::VBlank => timing::CYCLES_SCANLINE,
+        };
+        Some(length.saturating_sub(self.cycles).max(1))
+    }
+
+    /// VBlank割り込み要求を取得してクリア（要求があればtrueを一度だけ返す）
+    ///
+    /// Peripherals::tickが毎サイクル呼び出してIFへ反映する
+    pub fn take_vblank_interrupt(&mut self) -> bool {
+        core::mem::take(&mut self.vblank_interrupt)
+    }
+    
+    /// STAT割り込み要求を取得してクリア（要求があればtrueを一度だけ返す）
+    pub fn take_stat_interrupt(&mut self) -> bool {
+        core::mem::take(&mut self.stat_interrupt)
+    }
+    
     // VBlank割り込みフラグをクリア
     pub fn clear_vblank_interrupt(&mut self) {
         self.vblank_interrupt = false;
@@ -309,6 +666,8 @@ impl Ppu {
     pub fn write_vram(&mut self, address: u16, value: u8) {
         if self.mode != PpuMode::Drawing {
             self.vram.write(address - dmg::VRAM_START, value);
+        } else {
+            self.record_dropped_write(address);
         }
     }
 
@@ -321,11 +680,73 @@ impl Ppu {
     pub fn write_oam(&mut self, address: u16, value: u8) {
         if self.mode != PpuMode::Drawing && self.mode != PpuMode::OamScan {
             self.oam[(address - dmg::OAM_START) as usize] = value;
+        } else {
+            self.record_dropped_write(address);
+        }
+    }
+
+    // 破棄された書き込みを記録（track_dropped_writes有効時のみ）
+    fn record_dropped_write(&mut self, address: u16) {
+        if self.track_dropped_writes {
+            *self.dropped_writes.entry(address).or_insert(0) += 1;
+        }
+    }
+
+    /// 破棄されたVRAM/OAM書き込みの一覧（アドレス, 回数）をアドレス順に返す
+    ///
+    /// track_dropped_writesが有効な間の書き込みのみ記録される。
+    /// モード3中にVRAMへ書き込むなど、タイミングを誤ったゲームのデバッグ用
+    pub fn dropped_write_report(&self) -> Vec<(u16, u64)> {
+        self.dropped_writes.iter().map(|(&address, &count)| (address, count)).collect()
+    }
+
+    /// 破棄された書き込みの記録をクリア
+    pub fn clear_dropped_writes(&mut self) {
+        self.dropped_writes.clear();
+    }
+
+    /// OAMバグ: OAMスキャン中に現在PPUが読んでいる行を破損させる
+    ///
+    /// 行は8バイト（4ワード）単位。先頭ワードを直前の行と合成し、
+    /// 残り3ワードを直前の行からコピーする。行0は破損しない。
+    pub fn oam_bug_write(&mut self, pattern: OamBugPattern) {
+        if !self.oam_bug || self.mode != PpuMode::OamScan {
+            return;
+        }
+
+        // PPUは4サイクルごとに1行（2エントリ）を読み進める
+        let row = (self.cycles / 4) as usize;
+        if row == 0 || row >= 20 {
+            return;
+        }
+
+        let current = row * 8;
+        let previous = current - 8;
+        for i in 0..2 {
+            let a = self.oam[current + i];
+            let b = self.oam[previous + i];
+            let c = self.oam[previous + 4 + i];
+            self.oam[current + i] = match pattern {
+                OamBugPattern::Write => ((a ^ c) & (b ^ c)) ^ c,
+                OamBugPattern::Read => b | (a & c),
+            };
         }
+        self.oam.copy_within(previous + 2..previous + 8, current + 2);
     }
 
     // メモリ読み込み（レガシー: PPU単体テスト用）
+    // CPUと同様にモードによるアクセス制限を受ける（VRAM: Drawing中、OAM: OamScan/Drawing中は0xFF）
     pub fn read(&self, address: u16) -> u8 {
+        match address {
+            dmg::VRAM_START..=dmg::VRAM_END if self.mode == PpuMode::Drawing => 0xFF,
+            dmg::OAM_START..=dmg::OAM_END
+                if matches!(self.mode, PpuMode::OamScan | PpuMode::Drawing) => 0xFF,
+            _ => self.force_read(address),
+        }
+    }
+    
+    // メモリ読み込み（モードによるアクセス制限なし、描画・デバッグ表示用）
+    pub fn force_read(&self, address: u16) -> u8 {
         match address {
             dmg::VRAM_START..=dmg::VRAM_END => {
                 self.vram.read(address - dmg::VRAM_START)
@@ -333,7 +754,7 @@ impl Ppu {
             dmg::OAM_START..=dmg::OAM_END => {
                 self.oam[(address - dmg::OAM_START) as usize]
             },
-            io_registers::LCDC => self.registers.lcdc,
+            io_registers::LCDC => self.get_lcdc(),
             io_registers::STAT => self.registers.stat,
             io_registers::SCY => self.registers.scy,
             io_registers::SCX => self.registers.scx,
@@ -347,18 +768,12 @@ impl Ppu {
     // メモリ書き込み
     pub fn write(&mut self, address: u16, value: u8) {
         match address {
-            dmg::VRAM_START..=dmg::VRAM_END => {
-                if self.mode != PpuMode::Drawing {
-                    self.vram.write(address - dmg::VRAM_START, value);
-                }
-            },
-            dmg::OAM_START..=dmg::OAM_END => {
-                if self.mode != PpuMode::Drawing && self.mode != PpuMode::OamScan {
-                    self.oam[(address - dmg::OAM_START) as usize] = value;
-                }
-            },
-            io_registers::LCDC => self.registers.lcdc = value,
-            io_registers::STAT => self.registers.stat = (self.registers.stat & 0x07) | (value & 0xF8),
+            dmg::VRAM_START..=dmg::VRAM_END => self.write_vram(address, value),
+            dmg::OAM_START..=dmg::OAM_END => self.write_oam(address, value),
+            io_registers::LCDC => self.set_lcdc(value),
+            io_registers::STAT => {
+                self.registers.stat = (self.registers.stat & stat::READ_ONLY_MASK) | (value & !stat::READ_ONLY_MASK)
+            }
             io_registers::SCY => self.registers.scy = value,
             io_registers::SCX => self.registers.scx = value,
             io_registers::LY => {}, // LY は読み取り専用
@@ -369,6 +784,12 @@ impl Ppu {
     }
 }
 
+impl Default for Ppu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,4 +815,670 @@ mod tests {
         assert!(!ppu.step());
         assert_eq!(ppu.mode, PpuMode::Drawing);
     }
-}
\ No newline at end of file
+    
+    // OAM行nの各バイトを (n << 4) | byte_index で埋める
+    fn fill_oam_rows(ppu: &mut Ppu) {
+        for (i, byte) in ppu.oam.iter_mut().enumerate() {
+            *byte = (((i / 8) as u8) << 4) | (i % 8) as u8;
+        }
+    }
+    
+    #[test]
+    fn test_oam_bug_disabled_by_default() {
+        let mut ppu = Ppu::new();
+        fill_oam_rows(&mut ppu);
+        let before = ppu.oam;
+        
+        ppu.cycles = 8;
+        ppu.oam_bug_write(OamBugPattern::Write);
+        assert_eq!(ppu.oam, before);
+    }
+    
+    #[test]
+    fn test_oam_bug_write_pattern() {
+        let mut ppu = Ppu::new();
+        ppu.oam_bug = true;
+        fill_oam_rows(&mut ppu);
+        let before = ppu.oam;
+        
+        // サイクル8 → 行2を破損（行1が直前の行）
+        ppu.cycles = 8;
+        ppu.oam_bug_write(OamBugPattern::Write);
+        
+        // 先頭ワード: ((a ^ c) & (b ^ c)) ^ c
+        for i in 0..2 {
+            let (a, b, c) = (before[16 + i], before[8 + i], before[12 + i]);
+            assert_eq!(ppu.oam[16 + i], ((a ^ c) & (b ^ c)) ^ c);
+        }
+        // 残り3ワードは行1のコピー
+        assert_eq!(ppu.oam[18..24], before[10..16]);
+        // 他の行は変化しない
+        assert_eq!(ppu.oam[..16], before[..16]);
+        assert_eq!(ppu.oam[24..], before[24..]);
+    }
+    
+    #[test]
+    fn test_oam_bug_read_pattern() {
+        let mut ppu = Ppu::new();
+        ppu.oam_bug = true;
+        fill_oam_rows(&mut ppu);
+        let before = ppu.oam;
+        
+        ppu.cycles = 20; // 行5
+        ppu.oam_bug_write(OamBugPattern::Read);
+        
+        for i in 0..2 {
+            let (a, b, c) = (before[40 + i], before[32 + i], before[36 + i]);
+            assert_eq!(ppu.oam[40 + i], b | (a & c));
+        }
+        assert_eq!(ppu.oam[42..48], before[34..40]);
+    }
+    
+    #[test]
+    fn test_oam_bug_ignored_outside_oam_scan_and_row0() {
+        let mut ppu = Ppu::new();
+        ppu.oam_bug = true;
+        fill_oam_rows(&mut ppu);
+        let before = ppu.oam;
+        
+        // 行0は破損しない
+        ppu.cycles = 2;
+        ppu.oam_bug_write(OamBugPattern::Write);
+        assert_eq!(ppu.oam, before);
+        
+        // OAMスキャン以外では発生しない
+        ppu.mode = PpuMode::HBlank;
+        ppu.cycles = 8;
+        ppu.oam_bug_write(OamBugPattern::Write);
+        assert_eq!(ppu.oam, before);
+    }
+    
+    #[test]
+    fn test_debug_pixel_source_background() {
+        let mut ppu = Ppu::new();
+        ppu.set_pixel_source_recording(true);
+        
+        // タイルマップ0の(2, 1)にタイル5を配置
+        ppu.vram.write(0x1800 + 32 + 2, 5);
+        ppu.scanline = 10;
+        ppu.draw_scanline();
+        
+        // 画面(20, 10)はタイル(2, 1)から描画される
+        assert_eq!(
+            ppu.debug_pixel_source(20, 10),
+            PixelSource::Background { tile_index: 5, map_address: 0x9822 }
+        );
+        assert_eq!(
+            ppu.debug_pixel_source(0, 10),
+            PixelSource::Background { tile_index: 0, map_address: 0x9820 }
+        );
+        // 未描画のラインは記録なし
+        assert_eq!(ppu.debug_pixel_source(20, 11), PixelSource::None);
+    }
+    
+    #[test]
+    fn test_debug_pixel_source_sprite_and_window() {
+        let mut ppu = Ppu::new();
+        ppu.set_pixel_source_recording(true);
+        ppu.registers.lcdc = 0xB3; // LCD/BG/スプライト/ウィンドウ有効、ウィンドウはマップ0
+        ppu.registers.wy = 0;
+        ppu.registers.wx = 7 + 100; // 画面X=100からウィンドウ
+        
+        // タイル7: 全ピクセル色1
+        for i in 0..8 {
+            ppu.vram.write(7 * 16 + i * 2, 0xFF);
+        }
+        // OAMエントリ3: 画面(40, 10)にタイル7
+        ppu.oam[12] = 10 + 16;
+        ppu.oam[13] = 40 + 8;
+        ppu.oam[14] = 7;
+        
+        ppu.scanline = 10;
+        ppu.draw_scanline();
+        
+        assert_eq!(
+            ppu.debug_pixel_source(43, 10),
+            PixelSource::Sprite { tile_index: 7, oam_index: 3 }
+        );
+        assert_eq!(
+            ppu.debug_pixel_source(100, 10),
+            PixelSource::Window { tile_index: 0, map_address: 0x9800 }
+        );
+    }
+    
+    #[test]
+    fn test_sprite_stats_line_limit() {
+        let mut ppu = Ppu::new();
+        ppu.registers.lcdc = 0x93; // LCD/BG/スプライト有効
+        ppu.vram.load_tile(1, &[[1; 8]; 8]);
+        
+        // 12個のスプライトを画面Y=0〜7に重ならないよう横に並べる
+        for i in 0..12 {
+            ppu.oam[i * 4] = 16;
+            ppu.oam[i * 4 + 1] = (i * 8 + 8) as u8;
+            ppu.oam[i * 4 + 2] = 1;
+        }
+        
+        ppu.scanline = 0;
+        ppu.draw_scanline();
+        assert_eq!(
+            ppu.sprite_stats(),
+            sprites::SpriteStats { evaluated: 12, rendered: 10, dropped: 2, pixels_drawn: 80 }
+        );
+        
+        // 1フレーム全体では8ライン分の累計（VBlank中に参照）
+        let mut ppu_frame = Ppu::new();
+        ppu_frame.registers.lcdc = ppu.registers.lcdc;
+        ppu_frame.vram.load_tile(1, &[[1; 8]; 8]);
+        ppu_frame.oam = ppu.oam;
+        while !ppu_frame.step() {}
+        let stats = ppu_frame.sprite_stats();
+        assert_eq!(stats.rendered, 80);
+        assert_eq!(stats.dropped, 16);
+        assert_eq!(stats.pixels_drawn, 640);
+    }
+    
+    #[test]
+    fn test_sprite_lands_on_correct_row() {
+        let mut ppu = Ppu::new();
+        ppu.registers.lcdc = 0x93; // LCD/BG/スプライト有効
+        ppu.vram.load_tile(1, &[[3; 8]; 8]);
+        // 画面 (40, 70) に8x8スプライト
+        ppu.oam[0] = 70 + 16;
+        ppu.oam[1] = 40 + 8;
+        ppu.oam[2] = 1;
+        
+        // 統合経路（フレーム単位のスキャンライン描画）で1フレーム描画
+        while !ppu.step() {}
+        
+        let pixel = |x: usize, y: usize| {
+            let i = (y * 160 + x) * 3;
+            (ppu.framebuffer[i], ppu.framebuffer[i + 1], ppu.framebuffer[i + 2])
+        };
+        let dark = tiles::ColorConverter::dmg_to_rgb888(3);
+        let light = tiles::ColorConverter::dmg_to_rgb888(0);
+        for y in 70..78 {
+            assert_eq!(pixel(40, y), dark, "y={}", y);
+            assert_eq!(pixel(47, y), dark, "y={}", y);
+            assert_eq!(pixel(39, y), light, "y={}", y);
+            assert_eq!(pixel(48, y), light, "y={}", y);
+        }
+        assert_eq!(pixel(40, 69), light);
+        assert_eq!(pixel(40, 78), light);
+        assert_eq!(pixel(40, 0), light);
+    }
+    
+    #[test]
+    fn test_sprite_color0_transparent_regardless_of_palette() {
+        let mut ppu = Ppu::new();
+        ppu.registers.lcdc = 0x93; // LCD/BG/スプライト有効
+        ppu.registers.bgp = 0xE4;
+        // エントリ0を最暗色、エントリ1を最明色にしたパレット
+        ppu.registers.obp0 = 0x03;
+        ppu.registers.obp1 = 0x03;
+        // BGタイル0: 全て色2
+        ppu.vram.load_tile(0, &[[2; 8]; 8]);
+        // スプライトタイル1: 左4ピクセルが色0、右4ピクセルが色1
+        ppu.vram.load_tile(1, &[[0, 0, 0, 0, 1, 1, 1, 1]; 8]);
+        // OBP0とOBP1のスプライトを並べる
+        for (i, flags) in [0x00u8, 0x10].into_iter().enumerate() {
+            ppu.oam[i * 4] = 16;
+            ppu.oam[i * 4 + 1] = 8 + i as u8 * 8;
+            ppu.oam[i * 4 + 2] = 1;
+            ppu.oam[i * 4 + 3] = flags;
+        }
+        
+        while !ppu.step() {}
+        
+        let pixel = |x: usize| {
+            let i = x * 3;
+            (ppu.framebuffer[i], ppu.framebuffer[i + 1], ppu.framebuffer[i + 2])
+        };
+        let bg = tiles::ColorConverter::dmg_to_rgb888(2);
+        let sprite = tiles::ColorConverter::dmg_to_rgb888(0);
+        for base in [0, 8] {
+            // 色0はパレットに関係なく透明でBGが見える
+            for x in base..base + 4 {
+                assert_eq!(pixel(x), bg, "x={}", x);
+            }
+            for x in base + 4..base + 8 {
+                assert_eq!(pixel(x), sprite, "x={}", x);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_mode_timeline() {
+        let mut ppu = Ppu::new();
+        // 記録無効時は何も記録しない
+        for _ in 0..timing::CYCLES_SCANLINE {
+            ppu.step();
+        }
+        assert!(ppu.mode_timeline().iter().all(|&mode| mode == PpuMode::HBlank));
+        
+        let mut ppu = Ppu::new();
+        ppu.set_mode_timeline_recording(true);
+        for _ in 0..timing::CYCLES_SCANLINE * timing::SCANLINES_TOTAL as u32 {
+            ppu.step();
+        }
+        assert_eq!(ppu.scanline, 0);
+        
+        let timeline = ppu.mode_timeline();
+        for (line, &mode) in timeline.iter().enumerate() {
+            let expected = if line < 144 { PpuMode::Drawing } else { PpuMode::VBlank };
+            assert_eq!(mode, expected, "line={}", line);
+        }
+    }
+    
+    #[test]
+    fn test_animated_tile_renders_current_bytes_each_frame() {
+        let mut ppu = Ppu::new();
+        ppu.registers.lcdc = 0x91; // LCD/BG有効、タイルデータ0x8000
+        ppu.registers.bgp = 0xE4;
+        
+        // フレームごとにタイル0の内容を色1→2→3→0と書き換える（タイルマップは全てタイル0）
+        for color in [1u8, 2, 3, 0] {
+            ppu.vram.load_tile(0, &[[color; 8]; 8]);
+            while !ppu.step() {}
+            
+            let expected = tiles::ColorConverter::dmg_to_rgb888(color);
+            for (x, y) in [(0, 0), (159, 0), (80, 72), (159, 143)] {
+                let i = (y * 160 + x) * 3;
+                assert_eq!(
+                    (ppu.framebuffer[i], ppu.framebuffer[i + 1], ppu.framebuffer[i + 2]),
+                    expected,
+                    "color={} ({}, {})", color, x, y
+                );
+            }
+            // 次のフレームの先頭まで進める
+            while ppu.scanline != 0 {
+                ppu.step();
+            }
+        }
+    }
+    
+    // ウィンドウ用のテストシーン: ウィンドウ先頭行のタイル列が A(3,1,1,..), B(全2), C(全3)
+    fn setup_window_scene(wx: u8) -> Ppu {
+        let mut ppu = Ppu::new();
+        ppu.registers.lcdc = 0xF1; // LCD/ウィンドウ(マップ1)/BG有効、タイルデータ0x8000
+        ppu.registers.bgp = 0xE4;
+        ppu.registers.wy = 0;
+        ppu.registers.wx = wx;
+        
+        ppu.vram.load_tile(1, &[[3, 1, 1, 1, 1, 1, 1, 1]; 8]);
+        ppu.vram.load_tile(2, &[[2; 8]; 8]);
+        ppu.vram.load_tile(3, &[[3; 8]; 8]);
+        for (i, tile) in [1, 2, 3].into_iter().enumerate() {
+            ppu.vram.write(0x1C00 + i as u16, tile);
+        }
+        ppu.scanline = 0;
+        ppu
+    }
+    
+    #[test]
+    fn test_window_wx7_left_edge() {
+        let mut ppu = setup_window_scene(7);
+        ppu.draw_scanline();
+        assert_eq!(ppu.bg_color_ids[..10], [3, 1, 1, 1, 1, 1, 1, 1, 2, 2]);
+    }
+    
+    #[test]
+    fn test_window_wx3_partially_offscreen() {
+        // 画面X=0にウィンドウの4列目が来る
+        let mut ppu = setup_window_scene(3);
+        ppu.draw_scanline();
+        assert_eq!(ppu.bg_color_ids[..6], [1, 1, 1, 1, 2, 2]);
+        assert_eq!(ppu.bg_color_ids[12], 3);
+    }
+    
+    #[test]
+    fn test_window_wx0_left_edge_and_glitch() {
+        // グリッチ無効: 画面X=0にウィンドウの7列目
+        let mut ppu = setup_window_scene(0);
+        ppu.registers.scx = 3;
+        ppu.draw_scanline();
+        assert_eq!(ppu.bg_color_ids[..3], [1, 2, 2]);
+        assert_eq!(ppu.bg_color_ids[9], 3);
+        
+        // グリッチ有効: SCX & 7 = 3 だけさらに左にずれる（画面X=0にウィンドウの10列目）
+        let mut ppu = setup_window_scene(0);
+        ppu.registers.scx = 3;
+        ppu.wx0_glitch = true;
+        ppu.draw_scanline();
+        assert_eq!(ppu.bg_color_ids[..7], [2, 2, 2, 2, 2, 2, 3]);
+        
+        // SCXの端数が0ならずれない
+        let mut ppu = setup_window_scene(0);
+        ppu.wx0_glitch = true;
+        ppu.draw_scanline();
+        assert_eq!(ppu.bg_color_ids[..3], [1, 2, 2]);
+    }
+    
+    #[test]
+    fn test_debug_pixel_source_disabled() {
+        let mut ppu = Ppu::new();
+        ppu.scanline = 0;
+        ppu.draw_scanline();
+        assert_eq!(ppu.debug_pixel_source(0, 0), PixelSource::None);
+    }
+    
+    #[test]
+    fn test_legacy_read_mode_locks() {
+        let mut ppu = Ppu::new();
+        ppu.vram.write(0x0000, 0x5A);
+        ppu.oam[0] = 0xA5;
+        
+        // Drawing中: VRAM/OAMともにブロック
+        ppu.mode = PpuMode::Drawing;
+        assert_eq!(ppu.read(0x8000), 0xFF);
+        assert_eq!(ppu.read(0xFE00), 0xFF);
+        assert_eq!(ppu.force_read(0x8000), 0x5A);
+        assert_eq!(ppu.force_read(0xFE00), 0xA5);
+        
+        // OAMスキャン中: OAMのみブロック
+        ppu.mode = PpuMode::OamScan;
+        assert_eq!(ppu.read(0x8000), 0x5A);
+        assert_eq!(ppu.read(0xFE00), 0xFF);
+        
+        // HBlank中: どちらも読める
+        ppu.mode = PpuMode::HBlank;
+        assert_eq!(ppu.read(0x8000), 0x5A);
+        assert_eq!(ppu.read(0xFE00), 0xA5);
+    }
+    
+    #[test]
+    fn test_lcd_off_stops_ppu() {
+        let mut ppu = Ppu::new();
+        
+        // 描画モード中にLCDを無効化
+        for _ in 0..100 {
+            ppu.step();
+        }
+        assert_eq!(ppu.mode, PpuMode::Drawing);
+        ppu.registers.lcdc &= 0x7F;
+        
+        for _ in 0..1000 {
+            assert!(!ppu.step());
+        }
+        assert_eq!(ppu.mode, PpuMode::HBlank);
+        assert_eq!(ppu.registers.ly, 0);
+        assert_eq!(ppu.registers.stat & 0x03, 0);
+        
+        // LCD無効中はVRAMに書き込める
+        ppu.write_vram(0x8000, 0x5A);
+        assert_eq!(ppu.read_vram(0x8000), 0x5A);
+        
+        // 再有効化するとライン0のOAMスキャンから再開
+        ppu.registers.lcdc |= 0x80;
+        ppu.step();
+        assert_eq!(ppu.mode, PpuMode::OamScan);
+        assert_eq!(ppu.scanline, 0);
+    }
+    
+    #[test]
+    fn test_export_tile_atlas() {
+        let mut ppu = Ppu::new();
+        // タイル37（列5・行2）に行ごとに異なる色の模様を書き込む
+        let mut rows = [[0u8; 8]; 8];
+        for (y, row) in rows.iter_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = ((x + y) % 4) as u8;
+            }
+        }
+        ppu.vram.load_tile(37, &rows);
+        // 最後のタイル383は全て色3
+        ppu.vram.load_tile(383, &[[3u8; 8]; 8]);
+
+        let (atlas, width, height) = ppu.export_tile_atlas();
+        assert_eq!((width, height), (128, 192));
+        assert_eq!(atlas.len(), width * height);
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &color_id) in row.iter().enumerate() {
+                assert_eq!(atlas[(2 * 8 + y) * width + 5 * 8 + x], color_id);
+            }
+        }
+        assert_eq!(atlas[(23 * 8 + 7) * width + 15 * 8 + 7], 3);
+        assert_eq!(atlas[(23 * 8) * width + 15 * 8], 3);
+
+        // それ以外のタイルは色0のまま
+        let written = atlas.iter().filter(|&&c| c != 0).count();
+        let expected = rows.iter().flatten().filter(|&&c| c != 0).count() + 64;
+        assert_eq!(written, expected);
+    }
+
+    #[test]
+    fn test_lcd_enable_first_line_timing() {
+        let mut ppu = Ppu::new();
+        for _ in 0..1000 {
+            ppu.step();
+        }
+
+        // 無効化は即座に反映される
+        ppu.set_lcdc(0x11);
+        assert_eq!(ppu.get_lcdc(), 0x11);
+        assert_eq!(ppu.mode, PpuMode::HBlank);
+        assert_eq!(ppu.registers.ly, 0);
+        for _ in 0..500 {
+            ppu.step();
+        }
+
+        // 有効化直後: ライン0、STATはモード0
+        ppu.set_lcdc(0x91);
+        assert_eq!(ppu.registers.ly, 0);
+        assert_eq!(ppu.stat_mode(), 0);
+        assert_eq!(ppu.read(0xFF41) & 0x03, 0);
+
+        // 短縮されたOAMスキャン期間（76サイクル）はモード0のまま
+        let line0_scan = timing::CYCLES_OAM_SCAN - timing::LCD_ENABLE_LINE0_SKIP_CYCLES;
+        for _ in 0..line0_scan - 1 {
+            ppu.step();
+            assert_eq!(ppu.registers.stat & 0x03, 0);
+        }
+        // その後はモード3 → モード0
+        ppu.step();
+        assert_eq!(ppu.registers.stat & 0x03, 3);
+        for _ in 0..timing::CYCLES_DRAWING {
+            ppu.step();
+        }
+        assert_eq!(ppu.registers.stat & 0x03, 0);
+        for _ in 0..timing::CYCLES_HBLANK {
+            ppu.step();
+        }
+
+        // ライン1からは通常通りモード2
+        assert_eq!(ppu.registers.ly, 1);
+        assert_eq!(ppu.registers.stat & 0x03, 2);
+    }
+
+    #[test]
+    fn test_bg_scx_fine_scroll() {
+        let mut ppu = Ppu::new();
+        ppu.registers.lcdc = 0x91; // LCD/BG有効、タイルデータ0x8000
+        
+        // タイル1: 全ピクセル色3、マップ(1, 0)と(31, 0)に配置（他はタイル0=色0）
+        for i in 0..16 {
+            ppu.vram.write(16 + i, 0xFF);
+        }
+        ppu.vram.write(0x1800 + 1, 1);
+        ppu.vram.write(0x1800 + 31, 1);
+        
+        // SCXを1ずつ増やすと、色0→色3の境界が1ピクセルずつ左へ移動する
+        for scx in 0..=8u8 {
+            ppu.registers.scx = scx;
+            ppu.draw_scanline();
+            
+            let boundary = 8 - scx as usize;
+            for x in 0..16 {
+                let expected = if x >= boundary && x < boundary + 8 { 3 } else { 0 };
+                assert_eq!(ppu.bg_color_ids[x], expected, "SCX={} x={}", scx, x);
+            }
+        }
+        
+        // SCX=252: 左端はマップ右端(タイル31)の後半4ピクセル、その後256で折り返す
+        ppu.registers.scx = 252;
+        ppu.draw_scanline();
+        let line: Vec<u8> = ppu.bg_color_ids[..20].to_vec();
+        assert_eq!(line, [3, 3, 3, 3, 0, 0, 0, 0, 0, 0, 0, 0, 3, 3, 3, 3, 3, 3, 3, 3]);
+    }
+    
+    #[test]
+    fn test_bgp_latched_per_scanline() {
+        let mut ppu = Ppu::new();
+        ppu.registers.lcdc = 0x91; // LCD/BG有効、タイルデータ0x8000
+        // タイル0を全ピクセル色3に（タイルマップは全てタイル0）
+        ppu.vram.load_tile(0, &[[3; 8]; 8]);
+
+        // 各ラインのHBlank中にBGPを書き換え、色3の割り当てを0→1→2→3と変える（グラデーション）
+        for line in 0..8u8 {
+            ppu.registers.bgp = (line % 4) << 6;
+            while ppu.mode != PpuMode::Drawing {
+                ppu.step();
+            }
+            // モード3中の書き込みはこのラインには反映されない
+            ppu.registers.bgp = 0xFF;
+            while ppu.mode != PpuMode::HBlank {
+                ppu.step();
+            }
+        }
+
+        for line in 0..8usize {
+            let expected = tiles::ColorConverter::dmg_to_rgb888(line as u8 % 4);
+            for x in [0usize, 80, 159] {
+                let i = (line * 160 + x) * 3;
+                assert_eq!(
+                    (ppu.framebuffer[i], ppu.framebuffer[i + 1], ppu.framebuffer[i + 2]),
+                    expected,
+                    "line={} x={}", line, x
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_frame_count_and_ready_latch() {
+        let mut ppu = Ppu::new();
+        ppu.registers.lcdc = 0x91;
+        assert_eq!(ppu.frame_count(), 0);
+        assert!(!ppu.take_frame_ready());
+        let frame_cycles = timing::PpuTiming::new().cycles_per_frame;
+
+        // 1フレーム分のサイクルを戻り値を見ずに進める
+        for _ in 0..frame_cycles - 1 {
+            ppu.step();
+        }
+        assert_eq!(ppu.frame_count(), 0);
+        assert!(!ppu.take_frame_ready());
+
+        ppu.step();
+        assert_eq!(ppu.scanline, 0);
+        assert_eq!(ppu.frame_count(), 1);
+        assert!(ppu.take_frame_ready());
+        assert!(!ppu.take_frame_ready());
+
+        // 次のフレームの途中では増えない
+        for _ in 0..frame_cycles / 2 {
+            ppu.step();
+        }
+        assert_eq!(ppu.frame_count(), 1);
+        assert!(!ppu.take_frame_ready());
+    }
+
+    #[test]
+    fn test_dropped_write_report() {
+        let mut ppu = Ppu::new();
+        ppu.mode = PpuMode::Drawing;
+
+        // 無効時は破棄されるだけで記録しない
+        ppu.write_vram(0x8000, 0x11);
+        assert!(ppu.dropped_write_report().is_empty());
+
+        ppu.track_dropped_writes = true;
+        ppu.write_vram(0x8000, 0x11);
+        ppu.write_vram(0x8000, 0x22);
+        ppu.write_vram(0x9800, 0x33);
+        ppu.write_oam(0xFE00, 0x44);
+        assert_eq!(ppu.vram.read(0x0000), 0x00);
+        assert_eq!(ppu.vram.read(0x1800), 0x00);
+        assert_eq!(ppu.oam[0], 0x00);
+        assert_eq!(ppu.dropped_write_report(), [(0x8000, 2), (0x9800, 1), (0xFE00, 1)]);
+
+        // 書き込める期間の書き込みは記録されない
+        ppu.mode = PpuMode::HBlank;
+        ppu.write_vram(0x8000, 0x55);
+        assert_eq!(ppu.vram.read(0x0000), 0x55);
+        assert_eq!(ppu.dropped_write_report().len(), 3);
+
+        ppu.clear_dropped_writes();
+        assert!(ppu.dropped_write_report().is_empty());
+    }
+
+    #[test]
+    fn test_take_interrupts_once_per_event() {
+        let mut ppu = Ppu::new();
+        ppu.registers.stat = registers::stat::VBLANK_INTERRUPT;
+        assert!(!ppu.take_vblank_interrupt());
+        assert!(!ppu.take_stat_interrupt());
+        
+        while !ppu.step() {}
+        
+        // VBlank突入で1回ずつ
+        assert!(ppu.take_vblank_interrupt());
+        assert!(!ppu.take_vblank_interrupt());
+        assert!(ppu.take_stat_interrupt());
+        assert!(!ppu.take_stat_interrupt());
+        
+        // 次のVBlankまで再度立たない
+        for _ in 0..456 * 10 - 1 {
+            assert!(!ppu.step());
+            assert!(!ppu.take_vblank_interrupt());
+        }
+    }
+    
+    #[test]
+    fn test_stale_interrupts_cleared_on_new_frame() {
+        let mut ppu = Ppu::new();
+        ppu.registers.stat = registers::stat::VBLANK_INTERRUPT;
+        
+        // VBlankで立った要求を取り出さないまま次のフレームへ
+        while !ppu.step() {}
+        while ppu.scanline != 0 {
+            ppu.step();
+        }
+        assert!(!ppu.take_vblank_interrupt());
+        assert!(!ppu.take_stat_interrupt());
+    }
+    
+    #[test]
+    fn test_line_153_ly_reads_zero_early() {
+        let mut ppu = Ppu::new();
+
+        // ライン153の開始まで進める
+        while ppu.scanline != 153 {
+            ppu.step();
+        }
+        assert_eq!(ppu.cycles, 0);
+        assert_eq!(ppu.registers.ly, 153);
+
+        // 数サイクル後にはLYが0を示す（まだVBlank中）
+        for _ in 0..timing::LINE_153_LY_ZERO_CYCLES {
+            ppu.step();
+        }
+        assert_eq!(ppu.registers.ly, 0);
+        assert_eq!(ppu.ly(), 0);
+        assert_eq!(ppu.scanline, 153);
+        assert_eq!(ppu.mode, PpuMode::VBlank);
+
+        // ライン153の残りもLY=0のまま、456サイクルで次フレームのOAMスキャンへ
+        let remaining = timing::CYCLES_SCANLINE - timing::LINE_153_LY_ZERO_CYCLES;
+        for _ in 0..remaining - 1 {
+            ppu.step();
+            assert_eq!(ppu.registers.ly, 0);
+            assert_eq!(ppu.mode, PpuMode::VBlank);
+        }
+        ppu.step();
+        assert_eq!(ppu.scanline, 0);
+        assert_eq!(ppu.registers.ly, 0);
+        assert_eq!(ppu.mode, PpuMode::OamScan);
+    }
+}
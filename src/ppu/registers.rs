@@ -1,5 +1,33 @@
 // PPU関連のレジスタ
 
+// LCDC (0xFF40) のビット位置
+pub mod lcdc {
+    pub const LCD_ENABLE: u8 = 0x80;       // bit7: LCD有効
+    pub const WINDOW_TILEMAP: u8 = 0x40;   // bit6: ウィンドウタイルマップ (0=9800, 1=9C00)
+    pub const WINDOW_ENABLE: u8 = 0x20;    // bit5: ウィンドウ有効
+    pub const TILE_DATA: u8 = 0x10;        // bit4: BG/ウィンドウタイルデータ (0=8800, 1=8000)
+    pub const BG_TILEMAP: u8 = 0x08;       // bit3: BGタイルマップ (0=9800, 1=9C00)
+    pub const SPRITE_SIZE: u8 = 0x04;      // bit2: スプライトサイズ (0=8x8, 1=8x16)
+    pub const SPRITE_ENABLE: u8 = 0x02;    // bit1: スプライト有効
+    pub const BG_ENABLE: u8 = 0x01;        // bit0: BG/ウィンドウ有効
+}
+
+// STAT (0xFF41) のビット位置
+pub mod stat {
+    pub const LYC_INTERRUPT: u8 = 0x40;    // bit6: LY=LYC割り込み有効
+    pub const OAM_INTERRUPT: u8 = 0x20;    // bit5: モード2割り込み有効
+    pub const VBLANK_INTERRUPT: u8 = 0x10; // bit4: モード1割り込み有効
+    pub const HBLANK_INTERRUPT: u8 = 0x08; // bit3: モード0割り込み有効
+    pub const LYC_EQUAL: u8 = 0x04;        // bit2: LY=LYC一致フラグ (読み取り専用)
+    pub const MODE_MASK: u8 = 0x03;        // bit1-0: PPUモード (読み取り専用)
+
+    // 割り込み有効ビット（bit6-3、CPUから書き込み可能）
+    pub const INTERRUPT_MASK: u8 = LYC_INTERRUPT | OAM_INTERRUPT | VBLANK_INTERRUPT | HBLANK_INTERRUPT;
+    // PPUが更新する読み取り専用ビット（bit2-0）
+    pub const READ_ONLY_MASK: u8 = LYC_EQUAL | MODE_MASK;
+}
+
+#[derive(Clone)]
 pub struct PpuRegisters {
     pub lcdc: u8,    // 0xFF40 - LCD制御
     pub stat: u8,    // 0xFF41 - LCDステータス
@@ -33,71 +61,89 @@ impl PpuRegisters {
     
     // LCDC レジスタのビットフラグ
     pub fn is_lcd_enabled(&self) -> bool {
-        (self.lcdc & 0x80) != 0
+        (self.lcdc & lcdc::LCD_ENABLE) != 0
     }
     
     pub fn is_window_tilemap_high(&self) -> bool {
-        (self.lcdc & 0x40) != 0
+        (self.lcdc & lcdc::WINDOW_TILEMAP) != 0
     }
     
     pub fn is_window_enabled(&self) -> bool {
-        (self.lcdc & 0x20) != 0
+        (self.lcdc & lcdc::WINDOW_ENABLE) != 0
     }
     
     pub fn is_bg_window_tiledata_high(&self) -> bool {
-        (self.lcdc & 0x10) != 0
+        (self.lcdc & lcdc::TILE_DATA) != 0
     }
     
     pub fn is_bg_tilemap_high(&self) -> bool {
-        (self.lcdc & 0x08) != 0
+        (self.lcdc & lcdc::BG_TILEMAP) != 0
     }
     
     pub fn is_sprite_size_16(&self) -> bool {
-        (self.lcdc & 0x04) != 0
+        (self.lcdc & lcdc::SPRITE_SIZE) != 0
     }
     
     pub fn is_sprite_enabled(&self) -> bool {
-        (self.lcdc & 0x02) != 0
+        (self.lcdc & lcdc::SPRITE_ENABLE) != 0
     }
     
     pub fn is_bg_enabled(&self) -> bool {
-        (self.lcdc & 0x01) != 0
+        (self.lcdc & lcdc::BG_ENABLE) != 0
     }
     
     // STAT レジスタのビットフラグ
     pub fn is_lyc_interrupt_enabled(&self) -> bool {
-        (self.stat & 0x40) != 0
+        (self.stat & stat::LYC_INTERRUPT) != 0
     }
     
     pub fn is_oam_interrupt_enabled(&self) -> bool {
-        (self.stat & 0x20) != 0
+        (self.stat & stat::OAM_INTERRUPT) != 0
     }
     
     pub fn is_vblank_interrupt_enabled(&self) -> bool {
-        (self.stat & 0x10) != 0
+        (self.stat & stat::VBLANK_INTERRUPT) != 0
     }
     
     pub fn is_hblank_interrupt_enabled(&self) -> bool {
-        (self.stat & 0x08) != 0
+        (self.stat & stat::HBLANK_INTERRUPT) != 0
     }
     
     pub fn is_lyc_equal(&self) -> bool {
-        (self.stat & 0x04) != 0
+        (self.stat & stat::LYC_EQUAL) != 0
     }
     
+    /// LCDCの指定ビットを設定/解除（lcdc::*の定数を指定）
+    pub fn set_lcdc_flag(&mut self, flag: u8, enabled: bool) {
+        if enabled {
+            self.lcdc |= flag;
+        } else {
+            self.lcdc &= !flag;
+        }
+    }
+
+    /// STATの割り込み有効ビットを設定/解除（下位3bitは読み取り専用のため無視）
+    pub fn set_stat_flag(&mut self, flag: u8, enabled: bool) {
+        let flag = flag & stat::INTERRUPT_MASK;
+        if enabled {
+            self.stat |= flag;
+        } else {
+            self.stat &= !flag;
+        }
+    }
+
     pub fn get_mode(&self) -> u8 {
-        self.stat & 0x03
+        self.stat & stat::MODE_MASK
     }
     
     // BGP パレット変換 (2ビット -> 2ビット)
     pub fn get_bg_palette_color(&self, color_id: u8) -> u8 {
-        match color_id & 0x03 {
-            0 => self.bgp & 0x03,
-            1 => (self.bgp >> 2) & 0x03,
-            2 => (self.bgp >> 4) & 0x03,
-            3 => (self.bgp >> 6) & 0x03,
-            _ => unreachable!(),
-        }
+        Self::apply_palette(self.bgp, color_id)
+    }
+
+    /// パレット値（BGP/OBP形式）で色IDを変換（ラッチ済みのパレットに使う）
+    pub fn apply_palette(palette: u8, color_id: u8) -> u8 {
+        (palette >> ((color_id & 0x03) * 2)) & 0x03
     }
 
     // OBP0 パレット変換 (スプライトパレット0)
@@ -123,9 +169,18 @@ impl PpuRegisters {
     }
 }
 
+impl Default for PpuRegisters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    // (ビット, 対応するアクセサ)
+    type FlagAccessor = (u8, fn(&PpuRegisters) -> bool);
     
     #[test]
     fn test_lcdc_flags() {
@@ -141,6 +196,67 @@ mod tests {
         assert!(!registers.is_bg_enabled());
     }
     
+    #[test]
+    fn test_each_lcdc_bit() {
+        let accessors: [FlagAccessor; 8] = [
+            (lcdc::LCD_ENABLE, PpuRegisters::is_lcd_enabled),
+            (lcdc::WINDOW_TILEMAP, PpuRegisters::is_window_tilemap_high),
+            (lcdc::WINDOW_ENABLE, PpuRegisters::is_window_enabled),
+            (lcdc::TILE_DATA, PpuRegisters::is_bg_window_tiledata_high),
+            (lcdc::BG_TILEMAP, PpuRegisters::is_bg_tilemap_high),
+            (lcdc::SPRITE_SIZE, PpuRegisters::is_sprite_size_16),
+            (lcdc::SPRITE_ENABLE, PpuRegisters::is_sprite_enabled),
+            (lcdc::BG_ENABLE, PpuRegisters::is_bg_enabled),
+        ];
+
+        let mut registers = PpuRegisters::new();
+        for &(bit, accessor) in &accessors {
+            // 対象ビットのみ立てる: 該当アクセサのみtrue
+            registers.lcdc = bit;
+            for &(other, other_accessor) in &accessors {
+                assert_eq!(other_accessor(&registers), other == bit, "LCDC={:02X} bit={:02X}", bit, other);
+            }
+
+            // 対象ビットのみ落とす
+            registers.lcdc = 0xFF;
+            registers.set_lcdc_flag(bit, false);
+            assert!(!accessor(&registers));
+            assert_eq!(registers.lcdc, !bit);
+        }
+    }
+
+    #[test]
+    fn test_each_stat_bit() {
+        let accessors: [FlagAccessor; 5] = [
+            (stat::LYC_INTERRUPT, PpuRegisters::is_lyc_interrupt_enabled),
+            (stat::OAM_INTERRUPT, PpuRegisters::is_oam_interrupt_enabled),
+            (stat::VBLANK_INTERRUPT, PpuRegisters::is_vblank_interrupt_enabled),
+            (stat::HBLANK_INTERRUPT, PpuRegisters::is_hblank_interrupt_enabled),
+            (stat::LYC_EQUAL, PpuRegisters::is_lyc_equal),
+        ];
+
+        let mut registers = PpuRegisters::new();
+        for &(bit, _) in &accessors {
+            registers.stat = bit;
+            for &(other, other_accessor) in &accessors {
+                assert_eq!(other_accessor(&registers), other == bit, "STAT={:02X} bit={:02X}", bit, other);
+            }
+            assert_eq!(registers.get_mode(), 0);
+        }
+
+        // モードビット
+        registers.stat = 0x03;
+        assert_eq!(registers.get_mode(), 3);
+
+        // set_stat_flagは割り込み有効ビットのみ変更する
+        registers.stat = 0x00;
+        registers.set_stat_flag(stat::OAM_INTERRUPT, true);
+        registers.set_stat_flag(stat::LYC_EQUAL | stat::MODE_MASK, true);
+        assert_eq!(registers.stat, stat::OAM_INTERRUPT);
+        registers.set_stat_flag(stat::OAM_INTERRUPT, false);
+        assert_eq!(registers.stat, 0x00);
+    }
+
     #[test]
     fn test_bg_palette() {
         let mut registers = PpuRegisters::new();
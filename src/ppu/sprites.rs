@@ -15,9 +15,21 @@
 //   1スキャンラインあたり最大10スプライト
 //   X座標が小さいスプライトが優先（同じ場合はOAMインデックスが小さい方）
 
+use alloc::vec::Vec;
 use super::vram::Vram;
 use super::registers::PpuRegisters;
 use super::tiles::ColorConverter;
+use super::PixelSource;
+
+/// スプライト描画の出力先（1スキャンライン分）
+pub struct SpriteLineTargets<'a> {
+    /// 出力ラインバッファ (160 * 3 RGB)。X座標のみで添字を計算する
+    pub rgb: &'a mut [u8],
+    /// 描画元の記録 (160、Noneなら記録しない)
+    pub sources: Option<&'a mut [PixelSource]>,
+    /// パレット適用後の色番号 (160、Noneなら記録しない)
+    pub shades: Option<&'a mut [u8]>,
+}
 
 /// OAMスプライトエントリ
 #[derive(Debug, Clone, Copy)]
@@ -80,11 +92,37 @@ impl SpriteEntry {
     }
 }
 
+/// スプライト描画の統計（1ラインまたは1フレーム分の累計）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpriteStats {
+    /// スキャンラインに掛かっていたスプライト数（10個制限で落ちたものを含む）
+    pub evaluated: u32,
+    /// 描画対象になったスプライト数（1ライン最大10個）
+    pub rendered: u32,
+    /// 10個制限を超えて描画されなかったスプライト数
+    pub dropped: u32,
+    /// 描画したスプライトピクセル数（重なりは重複して数える）
+    pub pixels_drawn: u32,
+}
+
+impl SpriteStats {
+    /// 別の統計を加算
+    pub fn accumulate(&mut self, other: &SpriteStats) {
+        self.evaluated += other.evaluated;
+        self.rendered += other.rendered;
+        self.dropped += other.dropped;
+        self.pixels_drawn += other.pixels_drawn;
+    }
+}
+
 /// スプライトレンダラ
 pub struct SpriteRenderer;
 
 impl SpriteRenderer {
     /// OAMスキャン: 指定スキャンラインに表示されるスプライトを収集（最大10個）
+    ///
+    /// 実機のモード2と同じく、10個制限はX座標に関係なくOAM順で先に適用し、
+    /// 残ったスプライトだけをX座標で優先順に並べる（OAM順を毎フレーム回すゲームのちらつき表現）
     pub fn scan_oam(oam: &[u8; 160], scanline: u8, sprite_height: u8) -> Vec<SpriteEntry> {
         let mut sprites: Vec<SpriteEntry> = Vec::with_capacity(10);
 
@@ -117,12 +155,46 @@ impl SpriteRenderer {
         bg_color_ids: &[u8; 160],
         line_buffer: &mut [u8],
     ) {
+        Self::render_scanline_with_sources(
+            oam,
+            vram,
+            registers,
+            scanline,
+            bg_color_ids,
+            SpriteLineTargets { rgb: line_buffer, sources: None, shades: None },
+        );
+    }
+
+    /// スキャンラインに掛かるスプライト数（10個制限前）
+    pub fn count_on_scanline(oam: &[u8; 160], scanline: u8, sprite_height: u8) -> usize {
+        (0..40)
+            .filter(|&i| SpriteEntry::from_oam(oam, i).is_on_scanline(scanline, sprite_height))
+            .count()
+    }
+
+    /// スキャンラインにスプライトを描画し、描画したピクセルの描画元を記録
+    /// targets: 該当スキャンライン1行分の出力先（RGB・描画元・色番号）
+    /// 戻り値: このラインのスプライト統計
+    pub fn render_scanline_with_sources(
+        oam: &[u8; 160],
+        vram: &Vram,
+        registers: &PpuRegisters,
+        scanline: u8,
+        bg_color_ids: &[u8; 160],
+        targets: SpriteLineTargets,
+    ) -> SpriteStats {
+        let SpriteLineTargets { rgb: line_buffer, mut sources, mut shades } = targets;
+        debug_assert_eq!(line_buffer.len(), 160 * 3, "line_bufferは1スキャンライン分のスライスであること");
+        let mut stats = SpriteStats::default();
         if !registers.is_sprite_enabled() {
-            return;
+            return stats;
         }
 
         let sprite_height: u8 = if registers.is_sprite_size_16() { 16 } else { 8 };
         let sprites = Self::scan_oam(oam, scanline, sprite_height);
+        stats.evaluated = Self::count_on_scanline(oam, scanline, sprite_height) as u32;
+        stats.rendered = sprites.len() as u32;
+        stats.dropped = stats.evaluated - stats.rendered;
 
         // 逆順で描画（低優先度のスプライトから先に描画し、高優先度で上書き）
         for sprite in sprites.iter().rev() {
@@ -160,7 +232,7 @@ impl SpriteRenderer {
                 let screen_pixel_x = screen_x + pixel_x as i16;
 
                 // 画面外チェック
-                if screen_pixel_x < 0 || screen_pixel_x >= 160 {
+                if !(0..160).contains(&screen_pixel_x) {
                     continue;
                 }
                 let sx = screen_pixel_x as usize;
@@ -176,7 +248,7 @@ impl SpriteRenderer {
                 let pixel_high = (byte2 >> bit) & 1;
                 let color_id = pixel_low | (pixel_high << 1);
 
-                // 色ID 0は透明
+                // 色ID 0はパレット適用前に判定し、OBPのエントリ0の内容に関係なく透明
                 if color_id == 0 {
                     continue;
                 }
@@ -196,11 +268,26 @@ impl SpriteRenderer {
                 // RGB変換
                 let (r, g, b) = ColorConverter::dmg_to_rgb888(palette_color);
                 let idx = sx * 3;
+                debug_assert!(idx + 2 < 160 * 3, "スプライト書き込みがスキャンライン外: x={}", sx);
                 line_buffer[idx] = r;
                 line_buffer[idx + 1] = g;
                 line_buffer[idx + 2] = b;
+                stats.pixels_drawn += 1;
+
+                if let Some(shades) = shades.as_deref_mut() {
+                    shades[sx] = palette_color;
+                }
+
+                if let Some(sources) = sources.as_deref_mut() {
+                    sources[sx] = PixelSource::Sprite {
+                        tile_index: tile_id,
+                        oam_index: sprite.oam_index,
+                    };
+                }
             }
         }
+
+        stats
     }
 }
 
@@ -275,6 +362,31 @@ mod tests {
         assert_eq!(sprites.len(), 10); // 最大10個
     }
 
+    #[test]
+    fn test_oam_scan_limit_uses_oam_order() {
+        let mut oam = [0u8; 160];
+        // 12個のスプライトをスキャンライン0に配置。OAM順が後ろほどX座標が小さい
+        for i in 0..12 {
+            oam[i * 4] = 16;
+            oam[i * 4 + 1] = 8 + (12 - i as u8) * 8;
+        }
+
+        // X座標が最も小さいOAM 10, 11ではなく、OAM順で先頭の10個が残る
+        let sprites = SpriteRenderer::scan_oam(&oam, 0, 8);
+        let mut indices: Vec<u8> = sprites.iter().map(|s| s.oam_index).collect();
+        assert_eq!(sprites[0].oam_index, 9); // 残ったものの中でX最小
+        indices.sort();
+        assert_eq!(indices, (0..10).collect::<Vec<u8>>());
+
+        // OAM順を回転させると落ちるスプライトが入れ替わる（ちらつき）
+        oam.rotate_left(2 * 4);
+        let rotated = SpriteRenderer::scan_oam(&oam, 0, 8);
+        assert_eq!(rotated.len(), 10);
+        let xs: Vec<u8> = rotated.iter().map(|s| s.x).collect();
+        assert!(xs.contains(&16) && xs.contains(&24)); // 元のOAM 10, 11
+        assert!(!xs.contains(&104) && !xs.contains(&96)); // 元のOAM 0, 1
+    }
+
     #[test]
     fn test_oam_scan_sorting() {
         let mut oam = [0u8; 160];
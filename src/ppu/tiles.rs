@@ -1,15 +1,21 @@
 // タイルシステム実装
 
-use super::vram::{Vram, TileData, TileAddressingMode, TileMapSelect};
+use alloc::vec::Vec;
+use super::vram::{Vram, TileAddressingMode};
+#[cfg(feature = "std")]
+use super::vram::{TileData, TileMapSelect};
 
 pub struct TileRenderer {
     cache: TileCache,
+    /// キャッシュを使うかどうか（falseなら毎回VRAMから読み直す）
+    caching: bool,
 }
 
 impl TileRenderer {
     pub fn new() -> Self {
         Self {
             cache: TileCache::new(),
+            caching: true,
         }
     }
     
@@ -20,8 +26,14 @@ impl TileRenderer {
                       addressing_mode: TileAddressingMode,
                       palette: u8) -> [u8; 8 * 8] {
         
+        // VRAMが書き換えられていればキャッシュを破棄（アニメーションするタイル等）
+        if self.caching {
+            self.cache.sync_generation(vram.generation());
+        }
+        
         // キャッシュから取得を試行
-        if let Some(cached) = self.cache.get(tile_id, addressing_mode) {
+        let cached = if self.caching { self.cache.get(tile_id, addressing_mode) } else { None };
+        if let Some(cached) = cached {
             return self.apply_palette(cached, palette);
         }
         
@@ -37,7 +49,9 @@ impl TileRenderer {
         }
         
         // キャッシュに保存
-        self.cache.put(tile_id, addressing_mode, pixels);
+        if self.caching {
+            self.cache.put(tile_id, addressing_mode, pixels);
+        }
         
         // パレット適用
         self.apply_palette(pixels, palette)
@@ -64,12 +78,33 @@ impl TileRenderer {
     pub fn clear_cache(&mut self) {
         self.cache.clear();
     }
+
+    // キャッシュの有効/無効を切り替え（無効化時は古いエントリを破棄）
+    pub fn set_caching(&mut self, enabled: bool) {
+        self.caching = enabled;
+        if !enabled {
+            self.cache.clear();
+        }
+    }
+
+    // キャッシュが有効かどうか
+    pub fn is_caching(&self) -> bool {
+        self.caching
+    }
+}
+
+impl Default for TileRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // タイルキャッシュ（パフォーマンス向上のため）
 struct TileCache {
     entries: Vec<TileCacheEntry>,
     max_entries: usize,
+    // エントリ作成時のVRAM世代番号
+    generation: u64,
 }
 
 #[derive(Clone)]
@@ -85,13 +120,22 @@ impl TileCache {
         Self {
             entries: Vec::new(),
             max_entries: 64,  // 最大64タイルをキャッシュ
+            generation: 0,
+        }
+    }
+    
+    // VRAMの世代番号が変わっていたら全エントリを破棄
+    fn sync_generation(&mut self, generation: u64) {
+        if self.generation != generation {
+            self.entries.clear();
+            self.generation = generation;
         }
     }
     
     fn get(&mut self, tile_id: u8, addressing_mode: TileAddressingMode) -> Option<[u8; 64]> {
         for entry in &mut self.entries {
             if entry.tile_id == tile_id && 
-               std::mem::discriminant(&entry.addressing_mode) == std::mem::discriminant(&addressing_mode) {
+               core::mem::discriminant(&entry.addressing_mode) == core::mem::discriminant(&addressing_mode) {
                 entry.access_count += 1;
                 return Some(entry.pixels);
             }
@@ -103,7 +147,7 @@ impl TileCache {
         // 既存エントリがあるか確認
         for entry in &mut self.entries {
             if entry.tile_id == tile_id && 
-               std::mem::discriminant(&entry.addressing_mode) == std::mem::discriminant(&addressing_mode) {
+               core::mem::discriminant(&entry.addressing_mode) == core::mem::discriminant(&addressing_mode) {
                 entry.pixels = pixels;
                 entry.access_count += 1;
                 return;
@@ -162,8 +206,10 @@ impl ColorConverter {
 }
 
 // デバッグ用タイルビューア
+#[cfg(feature = "std")]
 pub struct TileViewer;
 
+#[cfg(feature = "std")]
 impl TileViewer {
     // タイルデータをコンソールに表示
     pub fn print_tile(tile_data: &TileData) {
@@ -239,6 +285,26 @@ mod tests {
         assert_eq!(pixels[24], 0); // 行3の色0 → パレット値0
     }
     
+    #[test]
+    fn test_tile_renderer_invalidated_by_vram_write() {
+        let mut vram = Vram::new();
+        let mut renderer = TileRenderer::new();
+        let palette = 0b11100100;
+        
+        vram.load_tile(0, &[[1; 8]; 8]);
+        assert_eq!(renderer.render_tile(&vram, 0, TileAddressingMode::Unsigned, palette), [1; 64]);
+        let generation = vram.generation();
+        
+        // 同じ世代ではキャッシュを使う
+        assert_eq!(renderer.render_tile(&vram, 0, TileAddressingMode::Unsigned, palette), [1; 64]);
+        assert_eq!(vram.generation(), generation);
+        
+        // VRAM書き込み後は新しい内容で描画される
+        vram.load_tile(0, &[[2; 8]; 8]);
+        assert!(vram.generation() > generation);
+        assert_eq!(renderer.render_tile(&vram, 0, TileAddressingMode::Unsigned, palette), [2; 64]);
+    }
+    
     #[test]
     fn test_color_converter() {
         let (r, g, b) = ColorConverter::dmg_to_rgb888(0);
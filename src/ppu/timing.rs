@@ -9,6 +9,9 @@ pub const CYCLES_SCANLINE: u32 = 456;    // 1スキャンライン合計
 pub const SCANLINES_VISIBLE: u8 = 144;   // 可視スキャンライン数
 pub const SCANLINES_TOTAL: u8 = 154;     // 総スキャンライン数
 pub const SCANLINES_VBLANK: u8 = 10;     // VBlankスキャンライン数
+pub const LINE_153_LY_ZERO_CYCLES: u32 = 4; // ライン153でLYが0に変わるまでのサイクル数
+pub const LCD_ENABLE_LINE0_SKIP_CYCLES: u32 = 4; // LCD有効化直後のライン0で短縮されるOAMスキャンのサイクル数
+pub const MODE_TIMELINE_SAMPLE_DOT: u32 = 160; // モードタイムラインの記録位置（ライン内ドット、可視ラインではMode 3中）
 
 pub const SCREEN_WIDTH: usize = 160;
 pub const SCREEN_HEIGHT: usize = 144;
@@ -49,6 +52,12 @@ impl PpuTiming {
     }
 }
 
+impl Default for PpuTiming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // スキャンライン位置からPPUモードを判定
 pub fn get_expected_mode(scanline: u8, cycle_in_line: u32) -> super::PpuMode {
     if scanline >= SCANLINES_VISIBLE {
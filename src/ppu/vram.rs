@@ -1,10 +1,17 @@
 // VRAM (Video RAM) 実装
 
+use core::cell::Cell;
+use crate::memory::MemPattern;
 use crate::memory_map::dmg;
 
+#[derive(Clone)]
 pub struct Vram {
     data: [u8; dmg::VRAM_SIZE],
     access_count: u32,
+    // 書き込みごとに増える世代番号（タイルキャッシュの有効性判定用）
+    generation: u64,
+    // タイルごとのハッシュキャッシュ（未計算・書き込み後はNone）
+    tile_hashes: [Cell<Option<u64>>; TILE_COUNT as usize],
 }
 
 impl Vram {
@@ -12,8 +19,17 @@ impl Vram {
         Self {
             data: [0; dmg::VRAM_SIZE],
             access_count: 0,
+            generation: 0,
+            tile_hashes: core::array::from_fn(|_| Cell::new(None)),
         }
     }
+
+    /// 指定パターンで初期化したVRAMを作成
+    pub fn new_with_pattern(pattern: MemPattern) -> Self {
+        let mut vram = Self::new();
+        pattern.fill(&mut vram.data);
+        vram
+    }
     
     // VRAM読み取り（相対アドレス）
     pub fn read(&self, address: u16) -> u8 {
@@ -29,6 +45,10 @@ impl Vram {
         if (address as usize) < dmg::VRAM_SIZE {
             self.data[address as usize] = value;
             self.access_count += 1;
+            self.generation = self.generation.wrapping_add(1);
+            if let Some(hash) = self.tile_hashes.get(address as usize / 16) {
+                hash.set(None);
+            }
         }
     }
     
@@ -49,6 +69,58 @@ impl Vram {
             }
         };
         
+        self.decode_tile(base_address)
+    }
+    
+    // タイル番号(0-383)で直接タイルデータを取得（アドレッシングモード非依存、デバッグ用）
+    // 範囲外の番号は空のタイルを返す
+    pub fn tile(&self, index: u16) -> TileData {
+        if index >= TILE_COUNT {
+            return TileData::new();
+        }
+        self.decode_tile(index * 16)
+    }
+    
+    // タイル番号(0-383)の16バイトのFNV-1aハッシュ（フロントエンドで未変更タイルの再処理を省く用）
+    // 計算結果はキャッシュし、該当タイルへの書き込みで無効化する。範囲外の番号は0を返す
+    pub fn tile_hash(&self, index: u16) -> u64 {
+        let Some(cached) = self.tile_hashes.get(index as usize) else {
+            return 0;
+        };
+        if let Some(hash) = cached.get() {
+            return hash;
+        }
+
+        let base_address = index as usize * 16;
+        let hash = self.data[base_address..base_address + 16]
+            .iter()
+            .fold(FNV_OFFSET_BASIS, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME));
+        cached.set(Some(hash));
+        hash
+    }
+    
+    // 8x8の色ID配列([y][x]、0-3)を2bppに変換してタイル番号(0-383)の位置に書き込む
+    // decode_tileの逆変換（テストシーン作成用）。範囲外の番号は無視する
+    pub fn load_tile(&mut self, index: u16, rows: &[[u8; 8]; 8]) {
+        if index >= TILE_COUNT {
+            return;
+        }
+        let base_address = index * 16;
+        for (y, row) in rows.iter().enumerate() {
+            let mut byte1 = 0u8;
+            let mut byte2 = 0u8;
+            for (x, &color_id) in row.iter().enumerate() {
+                let bit = 7 - x;
+                byte1 |= (color_id & 0x01) << bit;
+                byte2 |= ((color_id >> 1) & 0x01) << bit;
+            }
+            self.write(base_address + y as u16 * 2, byte1);
+            self.write(base_address + y as u16 * 2 + 1, byte2);
+        }
+    }
+    
+    // 指定アドレスの16バイトを8x8ピクセルにデコード
+    fn decode_tile(&self, base_address: u16) -> TileData {
         let mut tile_data = TileData::new();
         
         // 8行のタイルデータを読み取り
@@ -84,12 +156,40 @@ impl Vram {
         self.read(base_address + (y as u16) * 32 + (x as u16))
     }
     
+    // 符号付き/符号なしアドレッシングの両方から参照される領域か（相対アドレス0x0800-0x0FFF = $8800-$8FFF）
+    // BGとスプライトで同じタイルを共有するため、書き込みが意図せず両方に影響しうる（デバッグ用）
+    pub fn is_shared_region(address: u16) -> bool {
+        (SHARED_TILE_START..=SHARED_TILE_END).contains(&address)
+    }
+    
+    // 世代番号（書き込みのたびに増える。キャッシュ側で保存値と比較して内容の変化を検出する）
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    
     // 統計情報
     pub fn get_access_count(&self) -> u32 {
         self.access_count
     }
 }
 
+impl Default for Vram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// タイルデータ領域($8000-$97FF)に格納できるタイル数
+pub const TILE_COUNT: u16 = 384;
+
+// 符号付き/符号なしアドレッシングで共有されるタイルデータ領域（相対アドレス）
+const SHARED_TILE_START: u16 = 0x0800;
+const SHARED_TILE_END: u16 = 0x0FFF;
+
+// FNV-1a (64bit) の定数
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
 #[derive(Debug, Clone, Copy)]
 pub enum TileAddressingMode {
     Signed,    // $8800-$97FF (LCDC.4 = 0)
@@ -116,6 +216,7 @@ impl TileData {
     }
     
     // タイルデータを文字で表示（デバッグ用）
+    #[cfg(feature = "std")]
     pub fn print(&self) {
         let chars = [' ', '░', '▒', '█'];
         for row in &self.pixels {
@@ -127,6 +228,12 @@ impl TileData {
     }
 }
 
+impl Default for TileData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,4 +302,107 @@ mod tests {
         assert_eq!(vram.read_tile_map(TileMapSelect::Map0, 32, 0), 0);
         assert_eq!(vram.read_tile_map(TileMapSelect::Map0, 0, 32), 0);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_tile_by_absolute_index() {
+        let mut vram = Vram::new();
+        
+        // タイル200に縦縞パターン（色1と色2が交互、最終行は色3）
+        let base = 200 * 16;
+        for y in 0..8u16 {
+            let (low, high) = if y == 7 { (0xFF, 0xFF) } else { (0b10101010, 0b01010101) };
+            vram.write(base + y * 2, low);
+            vram.write(base + y * 2 + 1, high);
+        }
+        
+        let tile = vram.tile(200);
+        assert_eq!(tile.pixels[0], [1, 2, 1, 2, 1, 2, 1, 2]);
+        assert_eq!(tile.pixels[6], [1, 2, 1, 2, 1, 2, 1, 2]);
+        assert_eq!(tile.pixels[7], [3; 8]);
+        
+        // 符号付きモードのタイルID 200 ($8800 + 72*16) も同じタイルを指す
+        let signed = vram.read_tile_data(200, TileAddressingMode::Signed);
+        assert_eq!(signed.pixels, tile.pixels);
+        
+        // 範囲外は空のタイル
+        assert_eq!(vram.tile(TILE_COUNT).pixels, [[0; 8]; 8]);
+    }
+    
+    #[test]
+    fn test_load_tile_roundtrip() {
+        let mut vram = Vram::new();
+        
+        // 対角線と枠線を持つ4色のタイル
+        let mut rows = [[0u8; 8]; 8];
+        for (y, row) in rows.iter_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = if x == y {
+                    3
+                } else if x == 0 || x == 7 {
+                    2
+                } else if y == 0 || y == 7 {
+                    1
+                } else {
+                    0
+                };
+            }
+        }
+        
+        vram.load_tile(5, &rows);
+        assert_eq!(vram.read_tile_data(5, TileAddressingMode::Unsigned).pixels, rows);
+        
+        // 2bppのバイト配置: 1行目 = 3,1,1,1,1,1,1,2
+        assert_eq!(vram.read(5 * 16), 0b11111110);
+        assert_eq!(vram.read(5 * 16 + 1), 0b10000001);
+        
+        // 符号付き領域のタイル (番号256 = 符号付きID 0)
+        vram.load_tile(256, &rows);
+        assert_eq!(vram.read_tile_data(0, TileAddressingMode::Signed).pixels, rows);
+        
+        // 範囲外は無視
+        vram.load_tile(TILE_COUNT, &rows);
+    }
+    
+    #[test]
+    fn test_tile_hash() {
+        let mut vram = Vram::new();
+        let rows = [[1, 2, 3, 0, 1, 2, 3, 0]; 8];
+        vram.load_tile(3, &rows);
+        vram.load_tile(300, &rows);
+        
+        // 同一内容のタイルは同じハッシュ
+        assert_eq!(vram.tile_hash(3), vram.tile_hash(300));
+        assert_ne!(vram.tile_hash(3), vram.tile_hash(4));
+        
+        // 内容の異なるタイルは異なるハッシュ
+        let mut modified = rows;
+        modified[7][7] = 3;
+        vram.load_tile(4, &modified);
+        assert_ne!(vram.tile_hash(3), vram.tile_hash(4));
+        
+        // VRAM書き込み後はキャッシュが無効化されて更新される
+        let before = vram.tile_hash(300);
+        vram.write(300 * 16 + 15, 0xFF);
+        assert_ne!(vram.tile_hash(300), before);
+        vram.load_tile(300, &rows);
+        assert_eq!(vram.tile_hash(300), before);
+        
+        // タイルマップ領域への書き込みはタイルハッシュに影響しない
+        vram.write(0x1800, 0x01);
+        assert_eq!(vram.tile_hash(3), before);
+        assert_eq!(vram.tile_hash(TILE_COUNT), 0);
+    }
+    
+    #[test]
+    fn test_shared_region() {
+        assert!(Vram::is_shared_region(0x0800));
+        assert!(Vram::is_shared_region(0x0C00));
+        assert!(Vram::is_shared_region(0x0FFF));
+        
+        // 符号なし専用 ($8000-$87FF) と符号付き専用 ($9000-$97FF)
+        assert!((0x0000..=0x07FF).all(|addr| !Vram::is_shared_region(addr)));
+        assert!((0x1000..=0x17FF).all(|addr| !Vram::is_shared_region(addr)));
+        // タイルマップ領域
+        assert!(!Vram::is_shared_region(0x1800));
+    }
+}
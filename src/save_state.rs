@@ -0,0 +1,129 @@
+// src/save_state.rs
+// セーブステートと巻き戻しバッファ
+//
+// SaveStateはCPUと周辺機器の状態のメモリ上のスナップショット（ファイル形式は持たない）。
+// RewindBufferは一定フレームごとに取ったSaveStateを件数上限付きのリングバッファに保持し、
+// 新しいものから順に取り出して巻き戻す。ROMはカートリッジ間で共有されるため複製されない。
+
+use alloc::collections::VecDeque;
+use crate::cpu::Cpu;
+use crate::peripherals::PeripheralsState;
+
+/// Gameboy全体の状態のスナップショット
+#[derive(Clone)]
+pub struct SaveState {
+    pub(crate) cpu: Cpu,
+    pub(crate) peripherals: PeripheralsState,
+}
+
+/// 直近のセーブステートを保持する巻き戻しバッファ
+pub struct RewindBuffer {
+    states: VecDeque<SaveState>,
+    /// 保持する最大件数（超えたら古いものから破棄）
+    capacity: usize,
+    /// 何フレームごとに保存するか
+    interval: u32,
+    /// 前回の保存からの経過フレーム数
+    frames_since_capture: u32,
+}
+
+impl RewindBuffer {
+    /// capacity件まで、intervalフレームごと（0は1として扱う）に保存するバッファを作成
+    pub fn new(capacity: usize, interval: u32) -> Self {
+        Self {
+            states: VecDeque::with_capacity(capacity),
+            capacity,
+            interval: interval.max(1),
+            frames_since_capture: 0,
+        }
+    }
+
+    /// 1フレーム経過を通知し、保存するタイミングならtrueを返す
+    pub fn on_frame(&mut self) -> bool {
+        self.frames_since_capture += 1;
+        if self.frames_since_capture >= self.interval {
+            self.frames_since_capture = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// セーブステートを追加（上限を超えたら最も古いものを破棄）
+    pub fn push(&mut self, state: SaveState) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.states.len() == self.capacity {
+            self.states.pop_front();
+        }
+        self.states.push_back(state);
+    }
+
+    /// 最も新しいセーブステートを取り出す
+    pub fn pop(&mut self) -> Option<SaveState> {
+        self.frames_since_capture = 0;
+        self.states.pop_back()
+    }
+
+    /// 保持しているセーブステートの数
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// セーブステートを保持していないか
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// 保持する最大件数
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 全てのセーブステートを破棄
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.frames_since_capture = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peripherals::Peripherals;
+
+    fn state_with_a(a: u8) -> SaveState {
+        let mut cpu = Cpu::new();
+        cpu.registers.a = a;
+        SaveState {
+            cpu,
+            peripherals: Peripherals::new_with_dummy_bootrom().save_state(),
+        }
+    }
+
+    #[test]
+    fn test_rewind_buffer_ring() {
+        let mut buffer = RewindBuffer::new(3, 1);
+        for a in 0..5 {
+            buffer.push(state_with_a(a));
+        }
+        // 上限3件、古いものから破棄される
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.pop().unwrap().cpu.registers.a, 4);
+        assert_eq!(buffer.pop().unwrap().cpu.registers.a, 3);
+        assert_eq!(buffer.pop().unwrap().cpu.registers.a, 2);
+        assert!(buffer.pop().is_none());
+
+        let mut disabled = RewindBuffer::new(0, 1);
+        disabled.push(state_with_a(0));
+        assert!(disabled.is_empty());
+    }
+
+    #[test]
+    fn test_rewind_buffer_interval() {
+        let mut buffer = RewindBuffer::new(8, 3);
+        let captures: alloc::vec::Vec<bool> = (0..7).map(|_| buffer.on_frame()).collect();
+        assert_eq!(captures, [false, false, true, false, false, true, false]);
+    }
+}
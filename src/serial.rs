@@ -9,8 +9,13 @@
 //
 // 内部クロック使用時: 8192Hz (512 CPUサイクル/bit、4096サイクル/バイト)
 // 転送完了時(8ビットシフト後): SC bit7をクリアし、シリアル割り込みを要求
+//
+// 送信済みバイトは出力バッファに記録される（Blargg系テストROMの結果文字列の取得用）
+
+use alloc::vec::Vec;
 
 /// シリアル通信コントローラ
+#[derive(Clone)]
 pub struct Serial {
     /// シリアル転送データ (SB: 0xFF01)
     pub sb: u8,
@@ -21,7 +26,11 @@ pub struct Serial {
     /// 転送ビットカウンタ
     bit_counter: u8,
     /// 割り込み要求フラグ
-    pub interrupt_request: bool,
+    interrupt_request: bool,
+    /// 転送中の送信バイト（転送開始時のSB）
+    outgoing: u8,
+    /// 送信完了したバイト列
+    output: Vec<u8>,
 }
 
 /// 内部クロック: 1ビットあたり512 CPUサイクル (4,194,304 Hz / 8192 Hz)
@@ -35,9 +44,16 @@ impl Serial {
             transfer_counter: 0,
             bit_counter: 0,
             interrupt_request: false,
+            outgoing: 0x00,
+            output: Vec::new(),
         }
     }
 
+    /// 割り込み要求を取得してクリア（要求があればtrueを一度だけ返す）
+    pub fn take_interrupt(&mut self) -> bool {
+        core::mem::take(&mut self.interrupt_request)
+    }
+
     /// SBレジスタの読み取り
     pub fn read_sb(&self) -> u8 {
         self.sb
@@ -60,20 +76,41 @@ impl Serial {
         if value & 0x81 == 0x81 {
             self.transfer_counter = 0;
             self.bit_counter = 0;
+            self.outgoing = self.sb;
         }
     }
 
+    /// 送信完了したバイト列を取り出す（取り出し後はクリア）
+    pub fn take_output(&mut self) -> Vec<u8> {
+        core::mem::take(&mut self.output)
+    }
+
     /// 転送がアクティブかどうか
     pub fn is_transferring(&self) -> bool {
         self.sc & 0x80 != 0 && self.sc & 0x01 != 0
     }
 
-    /// シリアル通信を1サイクル進める
-    pub fn tick(&mut self) {
+    /// 転送完了の割り込みが発生するまでのサイクル数（転送中でなければNone）
+    pub fn cycles_until_interrupt(&self) -> Option<u32> {
         if !self.is_transferring() {
-            return;
+            return None;
         }
+        let bit_remaining = (CYCLES_PER_BIT - self.transfer_counter) as u32;
+        Some(bit_remaining + (7 - self.bit_counter as u32) * CYCLES_PER_BIT as u32)
+    }
 
+    /// シリアル通信を指定サイクル分進める（転送していない間は何もしない）
+    pub fn tick(&mut self, cycles: u32) {
+        for _ in 0..cycles {
+            if !self.is_transferring() {
+                return;
+            }
+            self.tick_cycle();
+        }
+    }
+
+    // 転送中のシリアル通信を1サイクル進める
+    fn tick_cycle(&mut self) {
         self.transfer_counter += 1;
 
         if self.transfer_counter >= CYCLES_PER_BIT {
@@ -88,11 +125,18 @@ impl Serial {
                 self.sc &= !0x80; // 転送フラグをクリア
                 self.bit_counter = 0;
                 self.interrupt_request = true;
+                self.output.push(self.outgoing);
             }
         }
     }
 }
 
+impl Default for Serial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,7 +190,7 @@ mod tests {
 
         // 8ビット転送 = 8 × 512 = 4096サイクル
         for _ in 0..4096 {
-            serial.tick();
+            serial.tick(1);
         }
 
         // 転送完了: SC bit7クリア、割り込み要求
@@ -154,6 +198,27 @@ mod tests {
         assert!(serial.interrupt_request);
     }
 
+    #[test]
+    fn test_serial_take_interrupt() {
+        let mut serial = Serial::new();
+        assert!(!serial.take_interrupt());
+
+        serial.write_sc(0x81);
+        for _ in 0..4096 {
+            serial.tick(1);
+        }
+
+        // 一度だけtrueを返し、次の転送完了まではfalse
+        assert!(serial.take_interrupt());
+        assert!(!serial.take_interrupt());
+
+        serial.write_sc(0x81);
+        for _ in 0..4096 {
+            serial.tick(1);
+        }
+        assert!(serial.take_interrupt());
+    }
+
     #[test]
     fn test_serial_receive_ff_without_connection() {
         let mut serial = Serial::new();
@@ -162,7 +227,7 @@ mod tests {
 
         // 接続なし → 全ビット1を受信
         for _ in 0..4096 {
-            serial.tick();
+            serial.tick(1);
         }
 
         assert_eq!(serial.read_sb(), 0xFF);
@@ -175,10 +240,26 @@ mod tests {
 
         // 転送を開始していない場合、tickは何もしない
         for _ in 0..5000 {
-            serial.tick();
+            serial.tick(1);
         }
 
         assert_eq!(serial.read_sb(), 0x42);
         assert!(!serial.interrupt_request);
     }
+
+    #[test]
+    fn test_serial_take_output() {
+        let mut serial = Serial::new();
+        for &byte in b"Passed" {
+            serial.write_sb(byte);
+            serial.write_sc(0x81);
+            for _ in 0..(CYCLES_PER_BIT as u32 * 8) {
+                serial.tick(1);
+            }
+            assert!(!serial.is_transferring());
+        }
+
+        assert_eq!(serial.take_output(), b"Passed".to_vec());
+        assert!(serial.take_output().is_empty());
+    }
 }
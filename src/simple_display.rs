@@ -17,49 +17,77 @@ impl SimpleDisplay {
         }
     }
     
-    // PPUフレームバッファをコンソールに表示
-    pub fn present_frame(&self, framebuffer: &[u8; 160 * 144 * 3]) {
+    // 画面クリアとヘッダー表示
+    fn print_header(&self) {
         println!("\x1b[2J\x1b[H"); // 画面クリア + カーソル移動
         println!("=== RustBoy GameBoy Emulator ===");
         println!("160x144 画面 (ASCII表示) - 2x2ピクセル縮小");
         println!();
-        
-        // 2x2ピクセルごとに1文字で表示（より詳細）
+    }
+    
+    // PPUフレームバッファをコンソールに表示
+    pub fn present_frame(&self, framebuffer: &[u8; 160 * 144 * 3]) {
+        self.print_header();
+        print!("{}", self.render_frame_to_string(framebuffer));
+        println!();
+        println!("Press Ctrl+C to exit");
+    }
+    
+    // PPUフレームバッファをASCII文字列に変換（2x2ピクセルごとに1文字、各行末に改行）
+    pub fn render_frame_to_string(&self, framebuffer: &[u8; 160 * 144 * 3]) -> String {
+        let mut output = String::with_capacity((self.width / 2 + 1) * (self.height / 2) * 3);
         for y in (0..self.height).step_by(2) {
             for x in (0..self.width).step_by(2) {
                 let pixel_index = (y * self.width + x) * 3;
-                if pixel_index + 2 < framebuffer.len() {
-                    let r = framebuffer[pixel_index];
-                    let g = framebuffer[pixel_index + 1];
-                    let b = framebuffer[pixel_index + 2];
-                    
-                    // GameBoy色を直接判定
-                    let char = match (r, g, b) {
-                        (0x0F, 0x38, 0x0F) => '█',  // 最暗色
-                        (0x30, 0x62, 0x30) => '▓',  // 暗
-                        (0x8B, 0xAC, 0x0F) => '▒',  // 明
-                        (0x9B, 0xBC, 0x0F) => '░',  // 最明色
-                        _ => {
-                            // その他の色は輝度で判定
-                            let brightness = ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8;
-                            match brightness {
-                                0..=63 => '█',    // 最暗
-                                64..=127 => '▓',  // 暗
-                                128..=191 => '▒', // 明
-                                192..=255 => '░', // 最明
-                            }
+                let r = framebuffer[pixel_index];
+                let g = framebuffer[pixel_index + 1];
+                let b = framebuffer[pixel_index + 2];
+                
+                // GameBoy色を直接判定
+                let char = match (r, g, b) {
+                    (0x0F, 0x38, 0x0F) => '█',  // 最暗色
+                    (0x30, 0x62, 0x30) => '▓',  // 暗
+                    (0x8B, 0xAC, 0x0F) => '▒',  // 明
+                    (0x9B, 0xBC, 0x0F) => '░',  // 最明色
+                    _ => {
+                        // その他の色は輝度で判定
+                        let brightness = ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8;
+                        match brightness {
+                            0..=63 => '█',    // 最暗
+                            64..=127 => '▓',  // 暗
+                            128..=191 => '▒', // 明
+                            192..=255 => '░', // 最明
                         }
-                    };
-                    print!("{}", char);
-                }
+                    }
+                };
+                output.push(char);
             }
-            println!();
+            output.push('\n');
         }
-        
+        output
+    }
+    
+    // 色番号バッファ（0=最明〜3=最暗）をコンソールに表示
+    // RGBからの逆引きを行わないため、パレットの色設定に影響されない
+    pub fn present_color_ids(&self, ids: &[u8; 160 * 144]) {
+        self.print_header();
+        print!("{}", self.render_color_ids_to_string(ids));
         println!();
         println!("Press Ctrl+C to exit");
     }
     
+    // 色番号バッファをASCII文字列に変換（2x2ピクセルごとに1文字、各行末に改行）
+    pub fn render_color_ids_to_string(&self, ids: &[u8; 160 * 144]) -> String {
+        let mut output = String::with_capacity((self.width / 2 + 1) * (self.height / 2) * 3);
+        for y in (0..self.height).step_by(2) {
+            for x in (0..self.width).step_by(2) {
+                output.push(shade_to_char(ids[y * self.width + x]));
+            }
+            output.push('\n');
+        }
+        output
+    }
+    
     // PPUテスト用デモパターン表示
     pub fn demo_patterns(&self) {
         println!("=== PPU デモパターン ===");
@@ -239,7 +267,7 @@ impl SimpleDisplay {
             print!("0x{:04X}: ", 0x8000 + row * 16);
             for col in 0..16 {
                 let addr = 0x8000 + row * 16 + col;
-                let value = ppu.read(addr);
+                let value = ppu.force_read(addr);
                 print!("{:02X} ", value);
             }
             println!();
@@ -250,7 +278,7 @@ impl SimpleDisplay {
             print!("0x{:04X}: ", 0x9800 + row * 16);
             for col in 0..16 {
                 let addr = 0x9800 + row * 16 + col;
-                let value = ppu.read(addr);
+                let value = ppu.force_read(addr);
                 print!("{:02X} ", value);
             }
             println!();
@@ -258,7 +286,26 @@ impl SimpleDisplay {
     }
 }
 
+impl Default for SimpleDisplay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 色番号をpresent_frameと同じシェード文字に変換
+// present_frame（RGB経由）と文字単位で同じ出力にするため、最明色も'░'で塗る。
+// gameboy_color_to_charは最明色を空白にする別の対応表なので、ここでは使わない
+fn shade_to_char(shade: u8) -> char {
+    match shade & 0x03 {
+        0 => '░',   // 最明色
+        1 => '▒',   // 明
+        2 => '▓',   // 暗
+        _ => '█',   // 最暗色
+    }
+}
+
 // GameBoy色をUnicode文字に変換
+// 最明色を空白にする単独表示用の対応表（present_frame/present_color_idsの出力とは異なる）
 pub fn gameboy_color_to_char(color_id: u8) -> char {
     match color_id & 0x03 {
         0 => ' ',   // 最明色 - 空白
@@ -287,4 +334,79 @@ mod tests {
         assert_eq!(gameboy_color_to_char(2), '▒');
         assert_eq!(gameboy_color_to_char(3), '█');
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_render_color_ids_to_string() {
+        let display = SimpleDisplay::new();
+        
+        // 各行を2ピクセルごとに色番号0→1→2→3で循環させる
+        let mut ids = [0u8; 160 * 144];
+        for y in 0..144 {
+            for x in 0..160 {
+                ids[y * 160 + x] = ((x / 2) % 4) as u8;
+            }
+        }
+        
+        let output = display.render_color_ids_to_string(&ids);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 72);
+        
+        let expected: String = "░▒▓█".repeat(20);
+        assert!(lines.iter().all(|line| *line == expected));
+    }
+    
+    #[test]
+    fn test_present_color_ids_from_ppu_frame() {
+        let mut ppu = Ppu::new();
+        ppu.write(0xFF40, 0x00); // LCD無効（VRAM/OAMに書き込めるように）
+
+        // タイル1〜3: 全面が色番号1〜3
+        for tile in 1..4u16 {
+            for row in 0..8u16 {
+                let addr = 0x8000 + tile * 16 + row * 2;
+                ppu.write(addr, if tile & 1 != 0 { 0xFF } else { 0x00 });
+                ppu.write(addr + 1, if tile & 2 != 0 { 0xFF } else { 0x00 });
+            }
+        }
+        // タイルマップ: 各行でタイル0→1→2→3を循環
+        for i in 0..32 * 32u16 {
+            ppu.write(0x9800 + i, (i % 4) as u8);
+        }
+        // スプライト0: 画面(0, 8)にタイル3（BGのタイル0の上）
+        ppu.write(0xFE00, 24);
+        ppu.write(0xFE01, 8);
+        ppu.write(0xFE02, 3);
+        ppu.write(0xFE03, 0x00);
+        ppu.write(0xFF47, 0xE4);
+        ppu.registers.obp0 = 0xE4;
+
+        ppu.write(0xFF40, 0x93); // LCD/BG/スプライト有効、タイルデータ0x8000
+        for _ in 0..70224 {
+            ppu.step();
+        }
+
+        let ids = ppu.color_ids();
+        assert_eq!(ids[0], 0);
+        assert_eq!(ids[8], 1);
+        assert_eq!(ids[8 * 160], 3); // スプライト
+
+        // RGB経由の表示と同じ文字列になる
+        let display = SimpleDisplay::new();
+        assert_eq!(display.render_color_ids_to_string(ids), display.render_frame_to_string(&ppu.framebuffer));
+        display.present_color_ids(ids);
+    }
+    
+    #[test]
+    fn test_render_frame_to_string_solid_color() {
+        let display = SimpleDisplay::new();
+        
+        // 全ピクセルを最暗色で塗りつぶす
+        let mut framebuffer = [0u8; 160 * 144 * 3];
+        for pixel in framebuffer.chunks_mut(3) {
+            pixel.copy_from_slice(&[0x0F, 0x38, 0x0F]);
+        }
+        
+        let expected = format!("{}\n", "█".repeat(80)).repeat(72);
+        assert_eq!(display.render_frame_to_string(&framebuffer), expected);
+    }
+}
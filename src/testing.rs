@@ -0,0 +1,136 @@
+// src/testing.rs
+// テスト用ROMビルダー（`testing`機能、またはクレート内のテストで有効）
+//
+// 結合テストや下流クレートがヘッダ組み立てを重複して書かずに済むよう、
+// 有効なカートリッジヘッダ（ロゴ・ヘッダチェックサム込み）とエントリポイントのプログラムを持つROMを生成する。
+//
+// 生成されるROMのレイアウト:
+//   0x0100-0x0103: NOP / JP 0x0150
+//   0x0104-0x0133: Nintendoロゴ
+//   0x0134-0x0143: タイトル
+//   0x0147-0x0149: カートリッジタイプ / ROMサイズ / RAMサイズ
+//   0x014D:        ヘッダチェックサム
+//   0x0150-:       プログラム本体（TestRomOptions::code）
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::boot_animation::NINTENDO_LOGO;
+
+/// プログラム本体を配置するアドレス（エントリポイントからここへジャンプする）
+pub const CODE_START: usize = 0x0150;
+
+/// テスト用ROMの構成
+#[derive(Debug, Clone)]
+pub struct TestRomOptions {
+    /// カートリッジタイプ (0x0147)
+    pub cartridge_type: u8,
+    /// ROMサイズコード (0x0148)、ROMは32KB << コードの大きさで生成（範囲外なら32KB）
+    pub rom_size: u8,
+    /// RAMサイズコード (0x0149)
+    pub ram_size: u8,
+    /// タイトル（最大16バイト、超えた分は切り捨て）
+    pub title: String,
+    /// 0x0150に配置するプログラム
+    pub code: Vec<u8>,
+}
+
+impl TestRomOptions {
+    pub fn new() -> Self {
+        Self {
+            cartridge_type: 0x00, // ROM ONLY
+            rom_size: 0x00,       // 32KB
+            ram_size: 0x00,
+            title: String::from("TEST"),
+            code: Vec::new(),
+        }
+    }
+}
+
+impl Default for TestRomOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ヘッダチェックサム (0x0134-0x014C) を計算
+pub fn header_checksum(rom: &[u8]) -> u8 {
+    rom[0x0134..=0x014C]
+        .iter()
+        .fold(0u8, |sum, &byte| sum.wrapping_sub(byte).wrapping_sub(1))
+}
+
+/// 構成に従ってテスト用ROMを生成
+///
+/// プログラムがROMに収まらない場合はpanicする
+pub fn build_test_rom(opts: TestRomOptions) -> Vec<u8> {
+    let size = if opts.rom_size <= 0x08 { 0x8000 << opts.rom_size } else { 0x8000 };
+    assert!(
+        CODE_START + opts.code.len() <= size,
+        "プログラムがROMに収まりません: {}バイト",
+        opts.code.len()
+    );
+    let mut rom = vec![0u8; size];
+
+    // エントリポイント: NOP / JP 0x0150
+    rom[0x0100..0x0104].copy_from_slice(&[0x00, 0xC3, CODE_START as u8, (CODE_START >> 8) as u8]);
+    rom[0x0104..0x0134].copy_from_slice(&NINTENDO_LOGO);
+
+    let title = opts.title.as_bytes();
+    let title_len = title.len().min(16);
+    rom[0x0134..0x0134 + title_len].copy_from_slice(&title[..title_len]);
+
+    rom[0x0147] = opts.cartridge_type;
+    rom[0x0148] = opts.rom_size;
+    rom[0x0149] = opts.ram_size;
+    rom[0x014D] = header_checksum(&rom);
+
+    rom[CODE_START..CODE_START + opts.code.len()].copy_from_slice(&opts.code);
+    rom
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::{Cartridge, CartridgeType};
+    use crate::gameboy::Gameboy;
+
+    #[test]
+    fn test_build_test_rom_header() {
+        let rom = build_test_rom(TestRomOptions {
+            cartridge_type: 0x03, // MBC1+RAM+BATTERY
+            rom_size: 0x02,       // 128KB
+            ram_size: 0x03,       // 32KB
+            title: String::from("BUILDER"),
+            ..TestRomOptions::default()
+        });
+        assert_eq!(rom.len(), 128 * 1024);
+        assert_eq!(&rom[0x0104..0x0134], &NINTENDO_LOGO);
+        assert_eq!(rom[0x014D], header_checksum(&rom));
+
+        let cart = Cartridge::new(rom).unwrap();
+        assert_eq!(cart.header.title, "BUILDER");
+        assert_eq!(cart.header.cartridge_type, CartridgeType::Mbc1RamBattery);
+        assert_eq!(cart.header.rom_banks, 8);
+        assert_eq!(cart.ram_size_bytes(), 32 * 1024);
+    }
+
+    #[test]
+    fn test_build_test_rom_runs_entry_program() {
+        let rom = build_test_rom(TestRomOptions {
+            code: vec![
+                0x3E, 0x42,       // LD A, 0x42
+                0xEA, 0x00, 0xC0, // LD (0xC000), A
+                0x18, 0xFE,       // JR -2
+            ],
+            ..TestRomOptions::default()
+        });
+
+        let mut gb = Gameboy::new_post_boot(Cartridge::new(rom).unwrap());
+        for _ in 0..4 {
+            gb.step().unwrap();
+        }
+        assert_eq!(gb.peripherals.read(0xC000), 0x42);
+        assert_eq!(gb.cpu.registers.pc, 0x0155);
+    }
+}